@@ -1,124 +1,575 @@
-use crate::regex;
-use input::RawInput;
+use alloc::AllocStats;
+use answers::{Answers, Verification};
+use bench::{Baseline, BenchConfig, BenchReport};
+use debug::Run;
+use input::{OwnedInput, RawInput};
+use panics::{Failure, Panic};
+use profile::SpanReport;
+use solver::{SinglePhase, Solver, TwoPhase};
+use std::cell::OnceCell;
 use std::fmt::{Debug, Display};
-use std::panic::RefUnwindSafe;
-use std::path::Path;
-use std::str::FromStr;
-use std::time::Instant;
-use std::{error, fs};
+use std::marker::PhantomData;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{fs, io};
 
+pub mod alloc;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod cli;
+pub mod debug;
+pub mod export;
 pub mod input;
-mod panics;
+pub mod panics;
+pub mod pool;
+pub mod profile;
+pub mod report;
+pub mod scaffold;
+pub mod solver;
+mod test_input;
+pub mod watch;
 
-pub fn solve<F1, F2, O1, O2>(day: usize, solve_part1: F1, solve_part2: F2)
+/// The input path that stands for stdin.
+pub const STDIN_PATH: &str = "-";
+
+pub use cancel::check_cancelled;
+pub use profile::span;
+
+/// An entry in the year registry, with the year's days in order.
+#[derive(Copy, Clone, Debug)]
+pub struct Year {
+    pub number: usize,
+    pub days: &'static [Day],
+}
+
+impl Year {
+    pub fn day(&self, number: usize) -> Option<&'static Day> {
+        self.days.iter().find(|day| day.number == number)
+    }
+}
+
+/// An entry in a year's day registry. Wraps the day's solvers in a plain function
+/// pointer so that days with different output types fit in the same table.
+#[derive(Copy, Clone, Debug)]
+pub struct Day {
+    pub number: usize,
+    /// Returns an error if the harness couldn't run the day, e.g. because an
+    /// input file is missing.
+    pub solve: fn(&Options) -> DayResult,
+}
+
+/// Which inputs to run each part on.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Run the test input, then the real input if the test passes.
+    #[default]
+    Both,
+    TestOnly,
+    RealOnly,
+}
+
+/// How to print the results.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    /// One JSON object per part, see `export`.
+    Json,
+    /// One CSV row per part, with the same columns as `Json`.
+    Csv,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Options {
+    /// Run only this part. Runs both parts if `None`.
+    pub part: Option<usize>,
+    pub mode: Mode,
+    /// Overrides the path of the real input. `-` reads it from stdin.
+    pub input_path: Option<PathBuf>,
+    /// Overrides the path of the test input. `-` reads it from stdin.
+    pub test_input_path: Option<PathBuf>,
+    /// Print only the answers.
+    pub quiet: bool,
+    /// Store the real outputs as the accepted answers.
+    pub record: bool,
+    /// Benchmark the real input with repeated runs.
+    pub bench: Option<BenchConfig>,
+    pub format: Format,
+    /// Cancel each part, and each example, after this long.
+    pub timeout: Option<Duration>,
+    /// Show the solvers' debug output.
+    pub verbose: bool,
+    /// Pass inputs to the solvers exactly as they are, instead of normalizing
+    /// their line endings and trailing whitespace.
+    pub strict_input: bool,
+}
+
+impl Options {
+    fn runs_part(&self, part: usize) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// A day's report, or why the harness couldn't run the day.
+pub type DayResult = Result<DayReport, String>;
+
+#[derive(Clone, Debug)]
+pub struct DayReport {
+    pub year: usize,
+    pub day: usize,
+    /// How long parsing the real input took, for days with a separate parse
+    /// step.
+    pub parse_duration: Option<Duration>,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PartReport {
+    pub part: usize,
+    /// One entry per test case with an expected output for this part.
+    pub tests: Vec<TestCaseReport>,
+    pub real: RealStatus,
+    /// The time spent in each span during the run on the real input.
+    pub spans: Vec<SpanReport>,
+    pub bench: Option<BenchReport>,
+}
+
+impl PartReport {
+    /// Combines the statuses of the test cases into one, or `None` if no test
+    /// case was run.
+    pub fn test_summary(&self) -> Option<&TestStatus> {
+        self.tests
+            .iter()
+            .map(|test| &test.status)
+            .find(|status| !matches!(status, TestStatus::Passed { .. }))
+            .or(self.tests.first().map(|test| &test.status))
+    }
+
+    /// Whether a test case or the real run failed, panicked or timed out, or
+    /// the real output doesn't match the stored answer. Parts that aren't
+    /// implemented don't count as failed.
+    pub fn failed(&self) -> bool {
+        let test_failed = self.tests.iter().any(|test| {
+            matches!(
+                test.status,
+                TestStatus::Failed { .. } | TestStatus::Panicked(_) | TestStatus::TimedOut
+            )
+        });
+        let real_failed = matches!(
+            self.real,
+            RealStatus::Solved {
+                verification: Verification::Wrong { .. },
+                ..
+            } | RealStatus::Panicked(_)
+                | RealStatus::TimedOut
+        );
+        test_failed || real_failed
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TestCaseReport {
+    pub name: Option<String>,
+    pub status: TestStatus,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TestStatus {
+    Passed { output: String },
+    Failed { output: String, expected: String },
+    NotImplemented,
+    Panicked(Panic),
+    TimedOut,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RealStatus {
+    Solved {
+        output: String,
+        duration: Duration,
+        /// Only measured with the `alloc-stats` feature.
+        memory: Option<AllocStats>,
+        verification: Verification,
+    },
+    NotImplemented,
+    Panicked(Panic),
+    TimedOut,
+    Skipped,
+}
+
+impl From<Failure> for RealStatus {
+    fn from(failure: Failure) -> Self {
+        match failure {
+            Failure::NotImplemented => RealStatus::NotImplemented,
+            Failure::Panicked(panic) => RealStatus::Panicked(panic),
+            Failure::TimedOut => RealStatus::TimedOut,
+        }
+    }
+}
+
+pub fn solve<F1, F2, O1, O2>(
+    options: &Options,
+    year: usize,
+    day: usize,
+    solve_part1: F1,
+    solve_part2: F2,
+) -> DayResult
 where
-    F1: RefUnwindSafe + Fn(RawInput) -> O1,
-    F2: RefUnwindSafe + Fn(RawInput) -> O2,
-    O1: Display + PartialEq + FromStr,
-    O2: Display + PartialEq + FromStr,
-    <O1 as FromStr>::Err: error::Error + 'static,
-    <O2 as FromStr>::Err: error::Error + 'static,
+    F1: Fn(RawInput) -> O1 + Send + Sync + 'static,
+    F2: Fn(RawInput) -> O2 + Send + Sync + 'static,
+    O1: Display + Send + 'static,
+    O2: Display + Send + 'static,
 {
-    let input_filename = format!("input/day{day:02}-input.txt");
-    let test_input_filename = format!("input/day{day:02}-test-input.txt");
-    let input_text = fs::read_to_string(Path::new(&input_filename)).unwrap();
-    let raw_test_input = fs::read_to_string(Path::new(&test_input_filename)).unwrap();
-    let test_input = TestInput::try_from(raw_test_input.as_str()).unwrap();
-    solve_part(SolvePartArgs {
-        part: 1,
-        solve: solve_part1,
-        input: &input_text,
-        test_input: test_input.text,
-        test_expected_output: test_input.part1_expected,
-    });
-    println!();
-    solve_part(SolvePartArgs {
-        part: 2,
-        solve: solve_part2,
-        input: &input_text,
-        test_input: test_input.text,
-        test_expected_output: test_input.part2_expected,
-    });
+    let solver = SinglePhase {
+        solve_part1,
+        solve_part2,
+    };
+    solve_with(options, year, day, Arc::new(solver))
 }
 
-#[derive(Copy, Clone, Debug)]
-struct SolvePartArgs<'a, F, O> {
+/// Like `solve`, but for days with a separate `parse` step whose output is
+/// shared by both parts.
+pub fn solve_parsed<P, F1, F2, I, O1, O2>(
+    options: &Options,
+    year: usize,
+    day: usize,
+    parse: P,
+    solve_part1: F1,
+    solve_part2: F2,
+) -> DayResult
+where
+    P: Fn(RawInput) -> I + Send + Sync + 'static,
+    F1: Fn(&I) -> O1 + Send + Sync + 'static,
+    F2: Fn(&I) -> O2 + Send + Sync + 'static,
+    I: Send + Sync + 'static,
+    O1: Display + Send + 'static,
+    O2: Display + Send + 'static,
+{
+    let solver = TwoPhase {
+        parse,
+        solve_part1,
+        solve_part2,
+        input: PhantomData,
+    };
+    solve_with(options, year, day, Arc::new(solver))
+}
+
+fn solve_with<S: Solver>(options: &Options, year: usize, day: usize, solver: Arc<S>) -> DayResult {
+    let input_path = match &options.input_path {
+        Some(path) => path.clone(),
+        None => input_file(year, day, "input"),
+    };
+    let test_input_path = match &options.test_input_path {
+        Some(path) => path.clone(),
+        None => input_file(year, day, "test-input"),
+    };
+    let input_text: Arc<str> = match options.mode {
+        Mode::TestOnly => Arc::from(""),
+        _ => read_input(options, &input_path)?.into(),
+    };
+    let raw_test_input = match options.mode {
+        Mode::RealOnly => String::new(),
+        _ => read_input(options, &test_input_path)?,
+    };
+    // Stored answers only apply to the default input.
+    let answers = match (options.mode, &options.input_path) {
+        (Mode::TestOnly, _) | (_, Some(_)) => Answers::default(),
+        (_, None) => {
+            Answers::load(year, day).map_err(|e| format!("Failed to load the answers: {e}"))?
+        }
+    };
+    let baseline = match options.bench {
+        Some(_) => {
+            Baseline::load().map_err(|e| format!("Failed to load the benchmark baseline: {e}"))?
+        }
+        None => Baseline::default(),
+    };
+    let test_cases = match options.mode {
+        Mode::RealOnly => vec![],
+        _ => test_input::parse_test_cases(&raw_test_input).map_err(|e| {
+            format!(
+                "Invalid test input in {}: {e}",
+                display_path(&test_input_path)
+            )
+        })?,
+    };
+    let (part1_cases, part2_cases): (Vec<_>, Vec<_>) = test_cases
+        .into_iter()
+        .map(|case| {
+            // Numbers the example's lines as they are in the file.
+            let offset = case.text.as_ptr() as usize - raw_test_input.as_ptr() as usize;
+            let line = 1 + raw_test_input[..offset].matches('\n').count();
+            let input =
+                OwnedInput::with_params(case.text.into(), case.params).starting_at_line(line);
+            let part1 = Example {
+                name: case.name,
+                input: input.clone(),
+                expected: case.part1_expected,
+            };
+            let part2 = Example {
+                name: case.name,
+                input,
+                expected: case.part2_expected,
+            };
+            (part1, part2)
+        })
+        .unzip();
+    // The real input is parsed at most once, and only if a part needs it.
+    let parsed = OnceCell::new();
+    let parse_real = || {
+        parsed
+            .get_or_init(|| {
+                let context = debug::Context {
+                    day,
+                    part: None,
+                    run: Run::Real,
+                };
+                let solver = Arc::clone(&solver);
+                let input = OwnedInput::new(Arc::clone(&input_text));
+                run_limited(options, context, move || {
+                    let start_time = Instant::now();
+                    let input = solver.parse(&input);
+                    (Arc::new(input), start_time.elapsed())
+                })
+            })
+            .as_ref()
+            .map(|(input, _)| Arc::clone(input))
+            .map_err(Clone::clone)
+    };
+    let mut parts = vec![];
+    if options.runs_part(1) {
+        parts.push(solve_part(SolvePartArgs {
+            options,
+            day,
+            part: 1,
+            solver: &solver,
+            solve: S::solve_part1,
+            parse_real: &parse_real,
+            examples: part1_cases,
+            answers: &answers,
+            baseline: baseline.get(year, day, 1),
+        }));
+    }
+    if options.runs_part(2) {
+        parts.push(solve_part(SolvePartArgs {
+            options,
+            day,
+            part: 2,
+            solver: &solver,
+            solve: S::solve_part2,
+            parse_real: &parse_real,
+            examples: part2_cases,
+            answers: &answers,
+            baseline: baseline.get(year, day, 2),
+        }));
+    }
+    let parse_duration = match parsed.get() {
+        Some(Ok((_, duration))) if S::HAS_PARSE_PHASE => Some(*duration),
+        _ => None,
+    };
+    Ok(DayReport {
+        year,
+        day,
+        parse_duration,
+        parts,
+    })
+}
+
+/// The directory with a year's input files.
+pub fn input_dir(year: usize) -> PathBuf {
+    PathBuf::from(format!("input/{year}"))
+}
+
+/// The path of one of a day's files in the input directory, e.g.
+/// `input/2024/day05-test-input.txt` for the kind `test-input`.
+pub fn input_file(year: usize, day: usize, kind: &str) -> PathBuf {
+    input_dir(year).join(format!("day{day:02}-{kind}.txt"))
+}
+
+/// Reads an input file, or stdin if the path is `-`, normalizing it unless
+/// the options ask for strict input.
+fn read_input(options: &Options, path: &Path) -> Result<String, String> {
+    let result = if path == Path::new(STDIN_PATH) {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(path)
+    };
+    let text = result.map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => format!("Input file not found: {}", display_path(path)),
+        _ => format!("Failed to read {}: {e}", display_path(path)),
+    })?;
+    if options.strict_input {
+        Ok(text)
+    } else {
+        Ok(input::normalize(&text))
+    }
+}
+
+fn display_path(path: &Path) -> String {
+    if path == Path::new(STDIN_PATH) {
+        "stdin".to_owned()
+    } else {
+        path.display().to_string()
+    }
+}
+
+struct SolvePartArgs<'a, S: Solver, F> {
+    options: &'a Options,
+    day: usize,
     part: usize,
+    solver: &'a Arc<S>,
     solve: F,
-    input: &'a str,
-    test_input: &'a str,
-    test_expected_output: Option<O>,
+    /// Returns the parsed real input, or why parsing failed.
+    parse_real: &'a dyn Fn() -> Result<Arc<S::Input>, Failure>,
+    examples: Vec<Example<'a>>,
+    answers: &'a Answers,
+    baseline: Option<Duration>,
 }
 
-fn solve_part<F, O>(
+#[derive(Clone, Debug)]
+struct Example<'a> {
+    name: Option<&'a str>,
+    /// Numbered from the line of the test-input file it starts on.
+    input: OwnedInput,
+    expected: Option<String>,
+}
+
+fn solve_part<S, F, O>(
     SolvePartArgs {
+        options,
+        day,
         part,
+        solver,
         solve,
-        input,
-        test_input,
-        test_expected_output,
-    }: SolvePartArgs<F, O>,
-) where
-    F: RefUnwindSafe + Fn(RawInput) -> O,
-    O: Display + PartialEq,
+        parse_real,
+        examples,
+        answers,
+        baseline,
+    }: SolvePartArgs<S, F>,
+) -> PartReport
+where
+    S: Solver,
+    F: Fn(&S, &S::Input) -> O + Copy + Send + 'static,
+    O: Display + Send + 'static,
 {
-    if let Some(expected) = test_expected_output {
-        if let Some(test_output) = panics::catching_todo(|| solve(RawInput::new(test_input))) {
-            if test_output == expected {
-                println!("Part {} test output: {} ✅", part, test_output);
-            } else {
-                println!("Part {} test output: {} ❌", part, test_output);
-                println!("          Expected: {}", expected);
-                return;
+    let mut tests = vec![];
+    for example in examples {
+        let Some(expected) = example.expected else {
+            continue;
+        };
+        let context = debug::Context {
+            day,
+            part: Some(part),
+            run: Run::Test(example.name.map(str::to_owned)),
+        };
+        let solver = Arc::clone(solver);
+        let status = match run_limited(options, context, move || {
+            let input = solver.parse(&example.input);
+            solve(&solver, &input)
+        }) {
+            Ok(output) => {
+                let output = output.to_string();
+                if answer::matches(&output, &expected) {
+                    TestStatus::Passed { output }
+                } else {
+                    TestStatus::Failed { output, expected }
+                }
             }
-        } else {
-            println!("Part {} not implemented.", part);
-            return;
+            Err(Failure::NotImplemented) => TestStatus::NotImplemented,
+            Err(Failure::Panicked(panic)) => TestStatus::Panicked(panic),
+            Err(Failure::TimedOut) => TestStatus::TimedOut,
+        };
+        let is_not_implemented = status == TestStatus::NotImplemented;
+        tests.push(TestCaseReport {
+            name: example.name.map(str::to_owned),
+            status,
+        });
+        if is_not_implemented {
+            break;
         }
     }
-    let result = panics::catching_todo(|| {
-        let start_time = Instant::now();
-        let output = solve(RawInput::new(input));
-        let duration = start_time.elapsed();
-        (output, duration)
+    let all_passed = tests
+        .iter()
+        .all(|test| matches!(test.status, TestStatus::Passed { .. }));
+    let skipped = PartReport {
+        part,
+        tests,
+        real: RealStatus::Skipped,
+        spans: vec![],
+        bench: None,
+    };
+    if options.mode == Mode::TestOnly || !all_passed {
+        return skipped;
+    }
+    let input = match parse_real() {
+        Ok(input) => input,
+        Err(failure) => {
+            return PartReport {
+                real: failure.into(),
+                ..skipped
+            }
+        }
+    };
+    let context = debug::Context {
+        day,
+        part: Some(part),
+        run: Run::Real,
+    };
+    let result = run_limited(options, context, {
+        let solver = Arc::clone(solver);
+        let input = Arc::clone(&input);
+        move || {
+            profile::collect(|| {
+                let start_time = Instant::now();
+                let (output, memory) = alloc::measure(|| solve(&solver, &input));
+                let duration = start_time.elapsed();
+                (output, duration, memory)
+            })
+        }
     });
-    if let Some((output, duration)) = result {
-        println!("Part {part} output: {output}");
-        println!("   ↑ Duration: {duration:.2?}");
-    } else {
-        println!("Part {} not implemented.", part);
+    let (real, spans) = match result {
+        Ok(((output, duration, memory), spans)) => {
+            let output = output.to_string();
+            let real = RealStatus::Solved {
+                verification: answers.verify(part, &output),
+                output,
+                duration,
+                memory,
+            };
+            (real, spans)
+        }
+        Err(failure) => (failure.into(), vec![]),
+    };
+    let bench = match (&options.bench, &real) {
+        (Some(config), RealStatus::Solved { .. }) => Some(BenchReport {
+            stats: bench::measure(config, || solve(solver, &input)),
+            baseline,
+        }),
+        _ => None,
+    };
+    PartReport {
+        real,
+        spans,
+        bench,
+        ..skipped
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-struct TestInput<'a, O1, O2> {
-    part1_expected: Option<O1>,
-    part2_expected: Option<O2>,
-    text: &'a str,
-}
-
-// Not implementing `FromStr` because of the lifetime bound.
-impl<'a, O1, O2> TryFrom<&'a str> for TestInput<'a, O1, O2>
+/// Runs a solver, catching its panics and giving up on it after the time
+/// limit. The context tags the solver's debug output, if it's shown. The
+/// solver is wrapped in `AssertUnwindSafe`, which is fine because nothing it
+/// could have left in a broken state is used after a panic.
+fn run_limited<F, O>(options: &Options, context: debug::Context, f: F) -> Result<O, Failure>
 where
-    O1: FromStr,
-    O2: FromStr,
-    <O1 as FromStr>::Err: error::Error + 'static,
-    <O2 as FromStr>::Err: error::Error + 'static,
+    F: FnOnce() -> O + Send + 'static,
+    O: Send + 'static,
 {
-    type Error = Box<dyn error::Error>;
-
-    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
-        let re = regex!(
-            r"(?s)^Part 1 expected: *([^\n]+)?
-Part 2 expected: *([^\n]+)?
- *
-(.*)$"
-        );
-        let caps = re.captures(s).ok_or("Invalid test input format.")?;
-        Ok(TestInput {
-            part1_expected: caps.get(1).map(|m| m.as_str().parse()).transpose()?,
-            part2_expected: caps.get(2).map(|m| m.as_str().parse()).transpose()?,
-            text: caps.get(3).unwrap().as_str(),
-        })
-    }
+    let debug_context = options.verbose.then_some(context);
+    cancel::with_time_limit(options.timeout, move || {
+        debug::with_context(debug_context, || panics::catching(AssertUnwindSafe(f)))
+    })
 }
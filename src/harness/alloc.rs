@@ -0,0 +1,131 @@
+//! Heap usage of the solvers, tracked by a counting global allocator when the
+//! `alloc-stats` feature is enabled. The counters are per thread, so that
+//! days running in parallel and the harness's helper threads don't affect
+//! each other's numbers.
+
+/// The heap usage of one run of a solver.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct AllocStats {
+    /// The most memory that was allocated at once beyond what was already
+    /// allocated when the run started.
+    pub peak_bytes: usize,
+    /// The number of allocations, including reallocations.
+    pub allocations: usize,
+}
+
+/// Calls `f`, returning its heap usage along with its result. The usage is
+/// `None` unless the `alloc-stats` feature is enabled.
+#[cfg(not(feature = "alloc-stats"))]
+pub fn measure<F, O>(f: F) -> (O, Option<AllocStats>)
+where
+    F: FnOnce() -> O,
+{
+    (f(), None)
+}
+
+#[cfg(feature = "alloc-stats")]
+pub use counting::measure;
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use super::AllocStats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    thread_local! {
+        // Signed, since memory may be freed on a different thread than the
+        // one that allocated it.
+        static CURRENT_BYTES: Cell<isize> = const { Cell::new(0) };
+        static PEAK_BYTES: Cell<isize> = const { Cell::new(0) };
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct CountingAllocator;
+
+    impl CountingAllocator {
+        fn record(change: isize, is_allocation: bool) {
+            // Ignore allocations during thread teardown, once the counters are
+            // gone.
+            let _ = CURRENT_BYTES.try_with(|current| {
+                let bytes = current.get() + change;
+                current.set(bytes);
+                let _ = PEAK_BYTES.try_with(|peak| peak.set(peak.get().max(bytes)));
+            });
+            if is_allocation {
+                let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            }
+        }
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                Self::record(layout.size() as isize, true);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            Self::record(-(layout.size() as isize), false);
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                Self::record(layout.size() as isize, true);
+            }
+            ptr
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                Self::record(new_size as isize - layout.size() as isize, true);
+            }
+            new_ptr
+        }
+    }
+
+    pub fn measure<F, O>(f: F) -> (O, Option<AllocStats>)
+    where
+        F: FnOnce() -> O,
+    {
+        let start_bytes = CURRENT_BYTES.get();
+        let start_allocations = ALLOCATIONS.get();
+        let old_peak = PEAK_BYTES.replace(start_bytes);
+        let output = f();
+        let peak = PEAK_BYTES.get();
+        PEAK_BYTES.set(old_peak.max(peak));
+        let stats = AllocStats {
+            peak_bytes: (peak - start_bytes).max(0) as usize,
+            allocations: ALLOCATIONS.get() - start_allocations,
+        };
+        (output, Some(stats))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_measure() {
+            let (_, stats) = measure(|| {
+                let small = vec![0u8; 1000];
+                drop(small);
+                let large = vec![0u8; 5000];
+                large.len()
+            });
+            let stats = stats.unwrap();
+            assert_eq!(stats.peak_bytes, 5000);
+            assert_eq!(stats.allocations, 2);
+
+            let (_, stats) = measure(|| 1 + 1);
+            assert_eq!(stats.unwrap(), AllocStats::default());
+        }
+    }
+}
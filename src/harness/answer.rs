@@ -0,0 +1,161 @@
+use crate::util::coords::{Coord2, Coord3, CoordNum};
+use std::fmt::{self, Display};
+
+/// Opens and closes a multi-line value in test-input and answers files, e.g.
+///
+/// ```text
+/// Part 2 expected: """
+/// #..#
+/// ####
+/// """
+/// ```
+pub const FENCE: &str = r#"""""#;
+
+/// Formats a compound answer, such as a coordinate or a list, as its parts
+/// separated by commas, e.g. `6,1` for `CommaSeparated(Coord2(6, 1))`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CommaSeparated<T>(pub T);
+
+/// A value with parts that `CommaSeparated` can join.
+pub trait Parts {
+    fn parts(&self) -> Vec<String>;
+}
+
+impl<T: Parts> Display for CommaSeparated<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.parts().join(","))
+    }
+}
+
+impl<A: Display, B: Display> Parts for (A, B) {
+    fn parts(&self) -> Vec<String> {
+        vec![self.0.to_string(), self.1.to_string()]
+    }
+}
+
+impl<A: Display, B: Display, C: Display> Parts for (A, B, C) {
+    fn parts(&self) -> Vec<String> {
+        vec![self.0.to_string(), self.1.to_string(), self.2.to_string()]
+    }
+}
+
+impl<T: Display> Parts for [T] {
+    fn parts(&self) -> Vec<String> {
+        self.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl<T: Display, const N: usize> Parts for [T; N] {
+    fn parts(&self) -> Vec<String> {
+        self.as_slice().parts()
+    }
+}
+
+impl<T: Display> Parts for Vec<T> {
+    fn parts(&self) -> Vec<String> {
+        self.as_slice().parts()
+    }
+}
+
+impl<T: CoordNum + Display> Parts for Coord2<T> {
+    fn parts(&self) -> Vec<String> {
+        (self.0, self.1).parts()
+    }
+}
+
+impl<T: CoordNum + Display> Parts for Coord3<T> {
+    fn parts(&self) -> Vec<String> {
+        (self.0, self.1, self.2).parts()
+    }
+}
+
+/// The form in which answers are compared: line endings and trailing
+/// whitespace on each line are ignored, as are leading and trailing blank
+/// lines.
+pub fn normalize(s: &str) -> String {
+    let lines = s.lines().map(str::trim_end).collect::<Vec<_>>();
+    lines.join("\n").trim_matches('\n').to_owned()
+}
+
+/// Whether an output matches an expected or accepted answer.
+pub fn matches(output: &str, expected: &str) -> bool {
+    normalize(output) == normalize(expected)
+}
+
+/// Reads a value that follows a label such as `Part 1 expected:`. If it's a
+/// fence, the value is made of the following lines up to the closing fence,
+/// which are consumed from `lines`. Returns `None` for an empty value.
+pub fn parse_value<'a, I>(value: &str, lines: &mut I) -> Result<Option<String>, String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let value = value.trim();
+    if value != FENCE {
+        return Ok(Some(value.to_owned()).filter(|value| !value.is_empty()));
+    }
+    let mut fenced = vec![];
+    for line in lines {
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line.trim_end() == FENCE {
+            return Ok(Some(fenced.join("\n")));
+        }
+        fenced.push(line);
+    }
+    Err(format!("Missing closing {FENCE}."))
+}
+
+/// Formats a value to follow a label, fencing it if it has multiple lines.
+pub fn format_value(value: &str) -> String {
+    if value.contains('\n') {
+        format!("{FENCE}\n{value}\n{FENCE}")
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comma_separated() {
+        assert_eq!(CommaSeparated(Coord2(6, 1)).to_string(), "6,1");
+        assert_eq!(CommaSeparated((1, "a", 2.5)).to_string(), "1,a,2.5");
+        assert_eq!(CommaSeparated(vec![4, 6, 3]).to_string(), "4,6,3");
+        assert_eq!(CommaSeparated(Vec::<usize>::new()).to_string(), "");
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("#..#\r\n####  \n\n", "\n#..#\n####"));
+        assert!(matches(" 42", " 42\n"));
+        assert!(!matches(" 42", "42"));
+        assert!(!matches("#..#\n####", "#..#\n###"));
+    }
+
+    #[test]
+    fn test_parse_value() {
+        let mut lines = ["#..#\n", "####\r\n", "\"\"\"\n", "rest\n"].into_iter();
+        assert_eq!(
+            parse_value(" \"\"\"", &mut lines),
+            Ok(Some("#..#\n####".to_owned()))
+        );
+        assert_eq!(lines.next(), Some("rest\n"));
+        assert_eq!(parse_value(" 12 ", &mut lines), Ok(Some("12".to_owned())));
+        assert_eq!(parse_value("", &mut lines), Ok(None));
+        assert!(parse_value(FENCE, &mut ["#..#"].into_iter()).is_err());
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value("12"), "12");
+        assert_eq!(format_value("#.\n.#"), "\"\"\"\n#.\n.#\n\"\"\"");
+        let formatted = format_value("#.\n.#");
+        let mut lines = formatted.lines();
+        let first = lines.next().unwrap();
+        assert_eq!(
+            parse_value(first, &mut lines),
+            Ok(Some("#.\n.#".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use crate::harness::{answer, input_file, DayReport, RealStatus};
+use crate::regex;
+use std::{error, fs, io};
+
+/// The accepted answers to a day's real input, stored alongside the input in
+/// `input/YEAR/dayNN-answers.txt` as lines of the form `Part 1: <answer>`.
+/// Multi-line answers are fenced the same way as in test-input files.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// The result of comparing a real output against the stored answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verification {
+    Verified,
+    Wrong {
+        accepted: String,
+    },
+    /// There is no stored answer for this part.
+    Unknown,
+}
+
+impl Answers {
+    /// Loads the stored answers for a day, or no answers if the file doesn't
+    /// exist.
+    pub fn load(year: usize, day: usize) -> Result<Self, Box<dyn error::Error>> {
+        match fs::read_to_string(input_file(year, day, "answers")) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e)?,
+        }
+    }
+
+    pub fn save(&self, year: usize, day: usize) -> io::Result<()> {
+        fs::write(input_file(year, day, "answers"), self.to_string())
+    }
+
+    pub fn get(&self, part: usize) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => panic!("Invalid part: {part}"),
+        }
+    }
+
+    pub fn set(&mut self, part: usize, answer: String) {
+        match part {
+            1 => self.part1 = Some(answer),
+            2 => self.part2 = Some(answer),
+            _ => panic!("Invalid part: {part}"),
+        }
+    }
+
+    pub fn verify(&self, part: usize, output: &str) -> Verification {
+        match self.get(part) {
+            Some(accepted) if answer::matches(output, accepted) => Verification::Verified,
+            Some(accepted) => Verification::Wrong {
+                accepted: accepted.to_owned(),
+            },
+            None => Verification::Unknown,
+        }
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let re = regex!(r"^Part ([12]):(.*)$");
+        let mut answers = Self::default();
+        let mut lines = s.lines();
+        while let Some(line) = lines.next() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let caps = re
+                .captures(line)
+                .ok_or_else(|| format!("Invalid answers line: {line}"))?;
+            if let Some(answer) = answer::parse_value(&caps[2], &mut lines)? {
+                answers.set(caps[1].parse()?, answer);
+            }
+        }
+        Ok(answers)
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in [1, 2] {
+            if let Some(answer) = self.get(part) {
+                writeln!(f, "Part {part}: {}", answer::format_value(answer))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stores the real outputs from a run as the accepted answers, keeping any
+/// stored answers for parts that weren't solved.
+pub fn record(report: &DayReport) -> Result<(), Box<dyn error::Error>> {
+    let mut answers = Answers::load(report.year, report.day)?;
+    for part in &report.parts {
+        if let RealStatus::Solved { output, .. } = &part.real {
+            answers.set(part.part, output.clone());
+        }
+    }
+    answers.save(report.year, report.day)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = "Part 1: 1234\nPart 2: ab,cd\n".parse::<Answers>().unwrap();
+        assert_eq!(answers.get(1), Some("1234"));
+        assert_eq!(answers.get(2), Some("ab,cd"));
+        assert_eq!(answers.to_string(), "Part 1: 1234\nPart 2: ab,cd\n");
+
+        let answers = "Part 2: 7\n".parse::<Answers>().unwrap();
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.to_string(), "Part 2: 7\n");
+
+        assert!("Part 3: 7\n".parse::<Answers>().is_err());
+
+        let s = "Part 1: \"\"\"\n#..#\n####\n\"\"\"\nPart 2: 7\n";
+        let answers = s.parse::<Answers>().unwrap();
+        assert_eq!(answers.get(1), Some("#..#\n####"));
+        assert_eq!(answers.get(2), Some("7"));
+        assert_eq!(answers.to_string(), s);
+    }
+
+    #[test]
+    fn test_verify() {
+        let answers = "Part 1: 1234\n".parse::<Answers>().unwrap();
+        assert_eq!(answers.verify(1, "1234"), Verification::Verified);
+        assert_eq!(
+            answers.verify(1, "1235"),
+            Verification::Wrong {
+                accepted: "1234".to_owned()
+            }
+        );
+        assert_eq!(answers.verify(1, "1234\n"), Verification::Verified);
+        assert_eq!(answers.verify(2, "1234"), Verification::Unknown);
+    }
+}
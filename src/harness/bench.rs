@@ -0,0 +1,219 @@
+use crate::harness::DayReport;
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use std::{error, fs, io};
+
+/// Where the medians of the previous benchmark runs are stored, one
+/// `<day> <part> <nanoseconds>` line per part.
+const BASELINE_PATH: &str = "bench-baseline.txt";
+
+/// The year of baseline lines written before the baseline had a year column,
+/// when 2024 was the only year.
+const LEGACY_YEAR: usize = 2024;
+
+/// Caps the number of runs when benchmarking against a time budget, so that
+/// very fast parts don't collect an unreasonable number of samples.
+const MAX_RUNS: usize = 100_000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BenchConfig {
+    /// Run each part exactly this many times. Overrides `budget`.
+    pub runs: Option<usize>,
+    /// Keep running each part until this much time has passed.
+    pub budget: Duration,
+    pub warmup_runs: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            runs: None,
+            budget: Duration::from_secs(1),
+            warmup_runs: 3,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BenchStats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BenchReport {
+    pub stats: BenchStats,
+    /// The median from the previous benchmark run of this part, if any.
+    pub baseline: Option<Duration>,
+}
+
+impl BenchReport {
+    /// The percent change of the median relative to the baseline.
+    pub fn change(&self) -> Option<f64> {
+        self.baseline.map(|baseline| {
+            (self.stats.median.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+        })
+    }
+}
+
+/// Warms up, then repeatedly times `f` as configured.
+pub fn measure<F, O>(config: &BenchConfig, f: F) -> BenchStats
+where
+    F: Fn() -> O,
+{
+    let warmup_start = Instant::now();
+    for _ in 0..config.warmup_runs {
+        black_box(f());
+        if warmup_start.elapsed() > config.budget / 10 {
+            break;
+        }
+    }
+    let mut durations = vec![];
+    let start = Instant::now();
+    loop {
+        let run_start = Instant::now();
+        let output = f();
+        durations.push(run_start.elapsed());
+        black_box(output);
+        let done = match config.runs {
+            Some(runs) => durations.len() >= runs,
+            None => start.elapsed() >= config.budget || durations.len() >= MAX_RUNS,
+        };
+        if done {
+            break;
+        }
+    }
+    get_stats(durations)
+}
+
+fn get_stats(mut durations: Vec<Duration>) -> BenchStats {
+    durations.sort_unstable();
+    let runs = durations.len();
+    let median = if runs.is_multiple_of(2) {
+        (durations[runs / 2 - 1] + durations[runs / 2]) / 2
+    } else {
+        durations[runs / 2]
+    };
+    let mean = durations.iter().sum::<Duration>() / runs as u32;
+    let variance = durations
+        .iter()
+        .map(|d| (d.as_secs_f64() - mean.as_secs_f64()).powi(2))
+        .sum::<f64>()
+        / runs as f64;
+    BenchStats {
+        runs,
+        min: durations[0],
+        median,
+        mean,
+        std_dev: Duration::from_secs_f64(variance.sqrt()),
+    }
+}
+
+/// The stored medians from previous benchmark runs, keyed by year, day and part.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Baseline(BTreeMap<(usize, usize, usize), Duration>);
+
+impl Baseline {
+    pub fn load() -> Result<Self, Box<dyn error::Error>> {
+        let s = match fs::read_to_string(BASELINE_PATH) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => Err(e)?,
+        };
+        Self::parse(&s)
+    }
+
+    /// Parses lines of the form `<year> <day> <part> <nanos>`, or the legacy
+    /// `<day> <part> <nanos>`, which are read as `LEGACY_YEAR`.
+    fn parse(s: &str) -> Result<Self, Box<dyn error::Error>> {
+        let mut baseline = Self::default();
+        for line in s.lines() {
+            let values = line.split_whitespace().collect::<Vec<_>>();
+            let (year, day, part, nanos) = match *values.as_slice() {
+                [year, day, part, nanos] => (year.parse()?, day, part, nanos),
+                [day, part, nanos] => (LEGACY_YEAR, day, part, nanos),
+                _ => Err(format!("Invalid benchmark baseline line: {line}"))?,
+            };
+            let median = Duration::from_nanos(nanos.parse()?);
+            baseline
+                .0
+                .insert((year, day.parse()?, part.parse()?), median);
+        }
+        Ok(baseline)
+    }
+
+    pub fn get(&self, year: usize, day: usize, part: usize) -> Option<Duration> {
+        self.0.get(&(year, day, part)).copied()
+    }
+}
+
+/// Stores the medians from a run as the new baseline, keeping the baseline of
+/// any part that wasn't benchmarked.
+pub fn save_baseline(reports: &[DayReport]) -> Result<(), Box<dyn error::Error>> {
+    let mut baseline = Baseline::load()?;
+    for day in reports {
+        for part in &day.parts {
+            if let Some(bench) = &part.bench {
+                let key = (day.year, day.day, part.part);
+                baseline.0.insert(key, bench.stats.median);
+            }
+        }
+    }
+    let s = baseline
+        .0
+        .iter()
+        .map(|((year, day, part), median)| format!("{year} {day} {part} {}\n", median.as_nanos()))
+        .collect::<String>();
+    fs::write(BASELINE_PATH, s)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_stats() {
+        let stats = get_stats([4, 1, 3, 2].map(Duration::from_millis).to_vec());
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.std_dev.as_micros(), 1118);
+
+        let stats = get_stats([5, 1, 3].map(Duration::from_millis).to_vec());
+        assert_eq!(stats.median, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_measure_fixed_runs() {
+        let config = BenchConfig {
+            runs: Some(7),
+            ..BenchConfig::default()
+        };
+        assert_eq!(measure(&config, || 1 + 1).runs, 7);
+    }
+
+    #[test]
+    fn test_change() {
+        let stats = get_stats(vec![Duration::from_millis(9)]);
+        let report = BenchReport {
+            stats,
+            baseline: Some(Duration::from_millis(10)),
+        };
+        assert!((report.change().unwrap() + 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_baseline() {
+        let baseline = Baseline::parse("2023 5 1 1000\n6 2 2000\n").unwrap();
+        assert_eq!(baseline.get(2023, 5, 1), Some(Duration::from_micros(1)));
+        assert_eq!(baseline.get(2024, 6, 2), Some(Duration::from_micros(2)));
+        assert_eq!(baseline.get(2024, 5, 1), None);
+        assert!(Baseline::parse("6 2\n").is_err());
+    }
+}
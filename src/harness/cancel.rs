@@ -0,0 +1,117 @@
+use crate::harness::panics::Failure;
+use crate::harness::pool::STACK_SIZE;
+use std::cell::RefCell;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+thread_local! {
+    /// Set once the time limit of the solver running on this thread has passed.
+    static CANCELLED: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// The panic payload used to unwind out of a cancelled solver.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Cancelled;
+
+/// Whether the solver running on this thread has exceeded its time limit.
+pub fn is_cancelled() -> bool {
+    CANCELLED.with_borrow(|flag| {
+        flag.as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    })
+}
+
+/// Unwinds out of the solver if it has exceeded its time limit. Solvers with
+/// loops that might not terminate should call this from inside them.
+pub fn check_cancelled() {
+    if is_cancelled() {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+/// Calls `f`, reporting it as timed out if it takes longer than `limit`.
+///
+/// With a limit, `f` runs on a thread of its own, and the harness stops
+/// waiting for it once the limit has passed. The solver is then cancelled,
+/// which only stops it once it calls `check_cancelled`. A solver that doesn't
+/// check is left running in the background until the process exits.
+pub fn with_time_limit<F, O>(limit: Option<Duration>, f: F) -> Result<O, Failure>
+where
+    F: FnOnce() -> Result<O, Failure> + Send + 'static,
+    O: Send + 'static,
+{
+    let Some(limit) = limit else {
+        return f();
+    };
+    let flag = Arc::new(AtomicBool::new(false));
+    let (result_sender, result_receiver) = mpsc::channel();
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn({
+            let flag = Arc::clone(&flag);
+            move || {
+                CANCELLED.set(Some(flag));
+                // Fails if the harness has stopped waiting.
+                let _ = result_sender.send(f());
+            }
+        })
+        .unwrap();
+    match result_receiver.recv_timeout(limit) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            flag.store(true, Ordering::Relaxed);
+            Err(Failure::TimedOut)
+        }
+        Err(RecvTimeoutError::Disconnected) => unreachable!("solver panics are caught"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::panics;
+    use std::time::Instant;
+
+    #[test]
+    fn test_with_time_limit() {
+        let result = with_time_limit(Some(Duration::from_secs(10)), || Ok(42));
+        assert_eq!(result, Ok(42));
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn test_with_time_limit_cancels() {
+        let result = with_time_limit(Some(Duration::from_millis(10)), || {
+            panics::catching(|| loop {
+                check_cancelled();
+                thread::sleep(Duration::from_millis(1));
+            })
+        });
+        assert_eq!(result, Err::<(), _>(Failure::TimedOut));
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn test_with_time_limit_reports_late_results() {
+        let result = with_time_limit(Some(Duration::from_millis(1)), || {
+            thread::sleep(Duration::from_millis(50));
+            Ok(42)
+        });
+        assert_eq!(result, Err(Failure::TimedOut));
+    }
+
+    #[test]
+    fn test_with_time_limit_stops_waiting() {
+        let start_time = Instant::now();
+        let result = with_time_limit(Some(Duration::from_millis(10)), || {
+            thread::sleep(Duration::from_secs(10));
+            Ok(42)
+        });
+        assert_eq!(result, Err(Failure::TimedOut));
+        assert!(start_time.elapsed() < Duration::from_secs(5));
+    }
+}
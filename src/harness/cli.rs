@@ -0,0 +1,319 @@
+use crate::harness::bench::BenchConfig;
+use crate::harness::{Format, Mode, Options, STDIN_PATH};
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage: advent-of-code-2024 [OPTIONS] [DAY...]
+       advent-of-code-2024 new <DAY>
+
+Arguments:
+  [DAY...]             Days to run, e.g. `5` or `1-3`. Defaults to the latest day.
+
+Commands:
+  new <DAY>            Create and register the module and test input for a new day,
+                       and its year if that's new.
+
+Options:
+  -y, --year <YEAR>    Run the days of YEAR. Defaults to the latest year.
+  -p, --part <PART>    Run only part 1 or part 2.
+  -t, --test-only      Run only the test input.
+  -r, --real-only      Run only the real input, skipping the test input.
+  -i, --input <PATH>   Read the real input from PATH instead of input/YEAR/dayNN-input.txt.
+                       Use `-` to read it from stdin.
+      --test-input <PATH>
+                       Read the test input from PATH instead of
+                       input/YEAR/dayNN-test-input.txt. Use `-` to read it from stdin.
+      --strict-input   Read inputs exactly as they are, without converting CRLF line
+                       endings or removing trailing whitespace.
+  -q, --quiet          Print only the answers.
+  -v, --verbose        Show the solvers' debug output on stderr.
+  -a, --all            Run every day of the year and print a summary table.
+  -f, --format <FMT>   Print the results as text (default), json or csv.
+      --record         Store the real outputs as the accepted answers.
+  -b, --bench          Benchmark each part and compare against the previous benchmark.
+      --runs <N>       Benchmark with exactly N runs per part. Implies --bench.
+      --budget <SECS>  Benchmark each part for SECS seconds (default 1). Implies --bench.
+      --timeout <SECS> Give up on a part after SECS seconds.
+  -j, --jobs <N>       Run up to N days in parallel (default 1).
+  -w, --watch          Run again whenever the days' input files change.
+  -h, --help           Print this message.";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Args {
+    /// The selected year, or `None` for the latest one.
+    pub year: Option<usize>,
+    /// The selected days, in the order given. Empty if none were given.
+    pub days: Vec<usize>,
+    pub options: Options,
+    /// Run every registered day of the year and print a summary table.
+    pub all: bool,
+    /// How many days to run in parallel.
+    pub jobs: usize,
+    /// Create a new day instead of running any.
+    pub new_day: Option<usize>,
+    /// Keep running the days whenever their inputs change.
+    pub watch: bool,
+    pub help: bool,
+}
+
+/// Parses the command-line arguments, not including the program name.
+pub fn parse_args<I>(args: I) -> Result<Args, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut result = Args::default();
+    let mut jobs = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-y" | "--year" => {
+                let year = next_value(&mut args, &arg)?;
+                match year.parse::<usize>() {
+                    Ok(year @ 2015..=9999) => result.year = Some(year),
+                    _ => Err(format!("Invalid year: {year}"))?,
+                }
+            }
+            "-p" | "--part" => {
+                let part = next_value(&mut args, &arg)?;
+                match part.as_str() {
+                    "1" | "2" => result.options.part = Some(part.parse().unwrap()),
+                    _ => Err(format!("Invalid part: {part}. Expected 1 or 2."))?,
+                }
+            }
+            "-t" | "--test-only" => set_mode(&mut result.options, Mode::TestOnly)?,
+            "-r" | "--real-only" => set_mode(&mut result.options, Mode::RealOnly)?,
+            "-i" | "--input" => {
+                result.options.input_path = Some(PathBuf::from(next_value(&mut args, &arg)?))
+            }
+            "--test-input" => {
+                result.options.test_input_path = Some(PathBuf::from(next_value(&mut args, &arg)?))
+            }
+            "--strict-input" => result.options.strict_input = true,
+            "-q" | "--quiet" => result.options.quiet = true,
+            "-v" | "--verbose" => result.options.verbose = true,
+            "-a" | "--all" => result.all = true,
+            "-f" | "--format" => {
+                let format = next_value(&mut args, &arg)?;
+                result.options.format = match format.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    _ => Err(format!(
+                        "Invalid format: {format}. Expected text, json or csv."
+                    ))?,
+                };
+            }
+            "--record" => result.options.record = true,
+            "-b" | "--bench" => {
+                result
+                    .options
+                    .bench
+                    .get_or_insert_with(BenchConfig::default);
+            }
+            "--runs" => {
+                let runs = next_value(&mut args, &arg)?;
+                let runs = match runs.parse::<usize>() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => Err(format!("Invalid number of runs: {runs}"))?,
+                };
+                result
+                    .options
+                    .bench
+                    .get_or_insert_with(BenchConfig::default)
+                    .runs = Some(runs);
+            }
+            "--budget" => {
+                let budget = next_value(&mut args, &arg)?;
+                let budget =
+                    parse_secs(&budget).ok_or_else(|| format!("Invalid budget: {budget}"))?;
+                result
+                    .options
+                    .bench
+                    .get_or_insert_with(BenchConfig::default)
+                    .budget = budget;
+            }
+            "--timeout" => {
+                let timeout = next_value(&mut args, &arg)?;
+                let timeout = parse_secs(&timeout)
+                    .filter(|timeout| !timeout.is_zero())
+                    .ok_or_else(|| format!("Invalid timeout: {timeout}"))?;
+                result.options.timeout = Some(timeout);
+            }
+            "-j" | "--jobs" => {
+                let value = next_value(&mut args, &arg)?;
+                match value.parse::<usize>() {
+                    Ok(n) if n > 0 => jobs = Some(n),
+                    _ => Err(format!("Invalid number of jobs: {value}"))?,
+                }
+            }
+            "-w" | "--watch" => result.watch = true,
+            "-h" | "--help" => result.help = true,
+            "new" => {
+                let day = next_value(&mut args, &arg)?;
+                match day.parse::<usize>() {
+                    Ok(day @ 1..=25) => result.new_day = Some(day),
+                    _ => Err(format!("Invalid day: {day}"))?,
+                }
+            }
+            _ if arg.starts_with('-') => Err(format!("Unknown option: {arg}"))?,
+            _ => result.days.extend(parse_days(&arg)?),
+        }
+    }
+    let options = &result.options;
+    if options.input_path.is_some() && (result.days.len() > 1 || result.all) {
+        Err("--input can only be used with a single day.")?
+    }
+    if options.test_input_path.is_some() && (result.days.len() > 1 || result.all) {
+        Err("--test-input can only be used with a single day.")?
+    }
+    let stdin = Some(PathBuf::from(STDIN_PATH));
+    if options.input_path == stdin && options.test_input_path == stdin {
+        Err("--input and --test-input cannot both read from stdin.")?
+    }
+    if result.watch && (options.input_path == stdin || options.test_input_path == stdin) {
+        Err("--watch cannot read input from stdin.")?
+    }
+    if result.options.record
+        && (result.options.input_path.is_some() || result.options.mode == Mode::TestOnly)
+    {
+        Err("--record requires running the default real input.")?
+    }
+    if result.options.bench.is_some() && result.options.mode == Mode::TestOnly {
+        Err("--bench cannot be used with --test-only.")?
+    }
+    if result.all && !result.days.is_empty() {
+        Err("--all cannot be combined with specific days.")?
+    }
+    if result.new_day.is_some() && (result.all || !result.days.is_empty()) {
+        Err("new cannot be combined with days to run.")?
+    }
+    if result.watch && result.options.record {
+        Err("--record cannot be used with --watch.")?
+    }
+    if let Some(jobs) = jobs {
+        if result.options.bench.is_some() && jobs > 1 {
+            Err("--bench cannot be used with --jobs, since parallel runs skew the timings.")?
+        }
+        result.jobs = jobs;
+    }
+    Ok(result)
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            year: None,
+            days: vec![],
+            options: Options::default(),
+            all: false,
+            jobs: 1,
+            new_day: None,
+            watch: false,
+            help: false,
+        }
+    }
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, option: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("Missing value for {option}."))
+}
+
+fn set_mode(options: &mut Options, mode: Mode) -> Result<(), String> {
+    if options.mode != Mode::Both && options.mode != mode {
+        Err("--test-only and --real-only cannot be used together.")?
+    }
+    options.mode = mode;
+    Ok(())
+}
+
+fn parse_secs(s: &str) -> Option<Duration> {
+    let secs = s.parse::<f64>().ok()?;
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Parses a single day, e.g. `5`, or an inclusive range of days, e.g. `1-3`.
+fn parse_days(s: &str) -> Result<Vec<usize>, String> {
+    let parse_day = |s: &str| s.parse::<usize>().map_err(|_| format!("Invalid day: {s}"));
+    match s.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (parse_day(start)?, parse_day(end)?);
+            if start > end {
+                Err(format!("Invalid day range: {s}"))?
+            }
+            Ok((start..=end).collect())
+        }
+        None => Ok(vec![parse_day(s)?]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse(&[]), Ok(Args::default()));
+        let args = parse(&["3", "5-7", "--part", "2", "-r", "-q"]).unwrap();
+        assert_eq!(args.days, vec![3, 5, 6, 7]);
+        assert_eq!(args.options.part, Some(2));
+        assert_eq!(args.options.mode, Mode::RealOnly);
+        assert!(args.options.quiet);
+        assert!(parse(&["3", "-v"]).unwrap().options.verbose);
+        assert!(
+            parse(&["3", "--strict-input"])
+                .unwrap()
+                .options
+                .strict_input
+        );
+        let args = parse(&["12", "-i", "other.txt"]).unwrap();
+        assert_eq!(args.options.input_path, Some(PathBuf::from("other.txt")));
+        let args = parse(&["12", "--test-input", "-"]).unwrap();
+        assert_eq!(args.options.test_input_path, Some(PathBuf::from("-")));
+        let args = parse(&["12", "--runs", "20", "--budget", "0.5"]).unwrap();
+        let bench = args.options.bench.unwrap();
+        assert_eq!(bench.runs, Some(20));
+        assert_eq!(bench.budget, Duration::from_millis(500));
+        let args = parse(&["-a", "--format", "csv"]).unwrap();
+        assert_eq!(args.options.format, Format::Csv);
+        let args = parse(&["6", "--timeout", "2.5"]).unwrap();
+        assert_eq!(args.options.timeout, Some(Duration::from_millis(2500)));
+        assert_eq!(parse(&["-a", "-j", "8"]).unwrap().jobs, 8);
+        assert_eq!(parse(&["new", "7"]).unwrap().new_day, Some(7));
+        let args = parse(&["--year", "2023", "new", "7"]).unwrap();
+        assert_eq!((args.year, args.new_day), (Some(2023), Some(7)));
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse(&["-p", "3"]).is_err());
+        assert!(parse(&["-p"]).is_err());
+        assert!(parse(&["-f", "xml"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+        assert!(parse(&["x"]).is_err());
+        assert!(parse(&["5-3"]).is_err());
+        assert!(parse(&["-t", "-r"]).is_err());
+        assert!(parse(&["1", "2", "-i", "other.txt"]).is_err());
+        assert!(parse(&["--all", "3"]).is_err());
+        assert!(parse(&["--record", "-t"]).is_err());
+        assert!(parse(&["--bench", "-t"]).is_err());
+        assert!(parse(&["--runs", "0"]).is_err());
+        assert!(parse(&["--budget", "-1"]).is_err());
+        assert!(parse(&["--timeout", "0"]).is_err());
+        assert!(parse(&["-j", "0"]).is_err());
+        assert!(parse(&["new", "26"]).is_err());
+        assert!(parse(&["-y", "24"]).is_err());
+        assert!(parse(&["new", "7", "8"]).is_err());
+        assert!(parse(&["-w", "--record"]).is_err());
+        assert!(parse(&["1", "2", "--test-input", "other.txt"]).is_err());
+        assert!(parse(&["1", "-i", "-", "--test-input", "-"]).is_err());
+        assert!(parse(&["1", "-w", "-i", "-"]).is_err());
+        assert!(parse(&["-a", "-j", "4", "--bench"]).is_err());
+        assert!(parse(&["3", "--record", "-i", "other.txt"]).is_err());
+    }
+}
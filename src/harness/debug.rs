@@ -0,0 +1,126 @@
+//! Diagnostics that solvers write with `RawInput::debug`. They are discarded
+//! unless the harness runs with `--verbose`, in which case each line goes to
+//! stderr tagged with the run it came from, e.g. `[day 15 part 2 real]`.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+
+thread_local! {
+    /// The run of the solver on this thread, if its output is being shown.
+    static CONTEXT: RefCell<Option<Context>> = const { RefCell::new(None) };
+}
+
+/// Identifies a run of a solver in its debug output.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Context {
+    pub day: usize,
+    /// `None` while parsing the real input, which both parts share.
+    pub part: Option<usize>,
+    pub run: Run,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Run {
+    /// A test case, with its name if it has one.
+    Test(Option<String>),
+    Real,
+}
+
+impl Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {}", self.day)?;
+        match self.part {
+            Some(part) => write!(f, " part {part}")?,
+            None => write!(f, " parse")?,
+        }
+        match &self.run {
+            Run::Test(Some(name)) => write!(f, " test ({name})"),
+            Run::Test(None) => write!(f, " test"),
+            Run::Real => write!(f, " real"),
+        }
+    }
+}
+
+/// Whether debug output from the solver running on this thread is shown.
+pub fn is_enabled() -> bool {
+    CONTEXT.with_borrow(Option::is_some)
+}
+
+/// Writes a message to stderr with each line tagged by the current run, if
+/// debug output is enabled. The message isn't formatted otherwise.
+pub fn log(message: impl Display) {
+    CONTEXT.with_borrow(|context| {
+        if let Some(context) = context {
+            eprintln!("{}", tag_lines(context, &message.to_string()));
+        }
+    })
+}
+
+/// Calls `f` with debug output tagged by `context`, or discarded if `context`
+/// is `None`.
+pub fn with_context<F, O>(context: Option<Context>, f: F) -> O
+where
+    F: FnOnce() -> O,
+{
+    struct Restore(Option<Context>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CONTEXT.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(CONTEXT.replace(context));
+    f()
+}
+
+fn tag_lines(context: &Context, message: &str) -> String {
+    if message.is_empty() {
+        return format!("[{context}]");
+    }
+    message
+        .lines()
+        .map(|line| format!("[{context}] {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_lines() {
+        let context = Context {
+            day: 15,
+            part: Some(2),
+            run: Run::Test(Some("small".to_owned())),
+        };
+        assert_eq!(
+            tag_lines(&context, "#.\n.#"),
+            "[day 15 part 2 test (small)] #.\n[day 15 part 2 test (small)] .#"
+        );
+        let context = Context {
+            day: 16,
+            part: None,
+            run: Run::Real,
+        };
+        assert_eq!(tag_lines(&context, ""), "[day 16 parse real]");
+    }
+
+    #[test]
+    fn test_with_context() {
+        assert!(!is_enabled());
+        let context = Context {
+            day: 1,
+            part: Some(1),
+            run: Run::Real,
+        };
+        with_context(Some(context), || {
+            assert!(is_enabled());
+            with_context(None, || assert!(!is_enabled()));
+            assert!(is_enabled());
+        });
+        assert!(!is_enabled());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::harness::answers::Verification;
+use crate::harness::{DayReport, Format, PartReport, RealStatus, TestStatus};
+use std::time::Duration;
+
+/// The columns of an exported record, in order. Scripts rely on these names,
+/// so only add to the end.
+const COLUMNS: [&str; 15] = [
+    "day",
+    "part",
+    "test",
+    "test_case",
+    "expected",
+    "actual",
+    "status",
+    "answer",
+    "verification",
+    "duration_ns",
+    "parse_duration_ns",
+    "error",
+    "peak_heap_bytes",
+    "allocations",
+    "year",
+];
+
+/// The values of a record, one per column.
+type Record<'a> = [Value<'a>; COLUMNS.len()];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Value<'a> {
+    Null,
+    Int(u128),
+    Str(&'a str),
+    String(String),
+}
+
+/// Formats the reports as one record per part. Returns `None` for the text
+/// format, which is printed by `report` instead.
+pub fn export(format: Format, reports: &[DayReport]) -> Option<String> {
+    let records = reports
+        .iter()
+        .flat_map(|day| day.parts.iter().map(move |part| part_record(day, part)))
+        .collect::<Vec<_>>();
+    match format {
+        Format::Text => None,
+        Format::Json => Some(to_json(&records)),
+        Format::Csv => Some(to_csv(&records)),
+    }
+}
+
+/// The values of each column for a part:
+///
+/// - `test`: `passed`, `failed`, `not_implemented`, `panicked` or `timed_out`,
+///   for the first test case that didn't pass, or `null` if no test case ran.
+/// - `test_case`, `expected`, `actual`: the name, expected output and actual
+///   output of that test case.
+/// - `status`: `solved`, `not_implemented`, `panicked`, `timed_out` or
+///   `skipped`, for the real input.
+/// - `answer`: the real output.
+/// - `verification`: `verified`, `wrong` or `unknown`, comparing the answer
+///   against the stored answer.
+/// - `duration_ns`: the real input's duration, or the median when
+///   benchmarking.
+/// - `parse_duration_ns`: the day's parse duration, if it has a parse step.
+/// - `error`: the message and location of the panic, if the test case or the
+///   real input panicked.
+/// - `peak_heap_bytes`, `allocations`: the real input's heap usage, only with
+///   the `alloc-stats` feature.
+fn part_record<'a>(day: &'a DayReport, part: &'a PartReport) -> Record<'a> {
+    let test_case = part
+        .tests
+        .iter()
+        .find(|test| Some(&test.status) == part.test_summary());
+    let (test, expected, actual) = match part.test_summary() {
+        Some(TestStatus::Passed { output }) => (Some("passed"), Some(output), Some(output)),
+        Some(TestStatus::Failed { output, expected }) => {
+            (Some("failed"), Some(expected), Some(output))
+        }
+        Some(TestStatus::NotImplemented) => (Some("not_implemented"), None, None),
+        Some(TestStatus::Panicked(_)) => (Some("panicked"), None, None),
+        Some(TestStatus::TimedOut) => (Some("timed_out"), None, None),
+        None => (None, None, None),
+    };
+    let (status, answer, verification, duration, memory) = match &part.real {
+        RealStatus::Solved {
+            output,
+            duration,
+            memory,
+            verification,
+        } => {
+            let verification = match verification {
+                Verification::Verified => "verified",
+                Verification::Wrong { .. } => "wrong",
+                Verification::Unknown => "unknown",
+            };
+            let duration = part.bench.map_or(*duration, |bench| bench.stats.median);
+            let verification = Some(verification);
+            (
+                "solved",
+                Some(output),
+                verification,
+                Some(duration),
+                *memory,
+            )
+        }
+        RealStatus::NotImplemented => ("not_implemented", None, None, None, None),
+        RealStatus::Panicked(_) => ("panicked", None, None, None, None),
+        RealStatus::TimedOut => ("timed_out", None, None, None, None),
+        RealStatus::Skipped => ("skipped", None, None, None, None),
+    };
+    let error = match (part.test_summary(), &part.real) {
+        (Some(TestStatus::Panicked(panic)), _) | (_, RealStatus::Panicked(panic)) => {
+            Some(panic.to_string())
+        }
+        _ => None,
+    };
+    let str_or_null = |s: Option<&'a str>| s.map_or(Value::Null, Value::Str);
+    let nanos_or_null = |d: Option<Duration>| d.map_or(Value::Null, |d| Value::Int(d.as_nanos()));
+    [
+        Value::Int(day.day as u128),
+        Value::Int(part.part as u128),
+        str_or_null(test),
+        str_or_null(test_case.and_then(|test| test.name.as_deref())),
+        str_or_null(expected.map(String::as_str)),
+        str_or_null(actual.map(String::as_str)),
+        Value::Str(status),
+        str_or_null(answer.map(String::as_str)),
+        str_or_null(verification),
+        nanos_or_null(duration),
+        nanos_or_null(day.parse_duration),
+        error.map_or(Value::Null, Value::String),
+        memory.map_or(Value::Null, |memory| Value::Int(memory.peak_bytes as u128)),
+        memory.map_or(Value::Null, |memory| Value::Int(memory.allocations as u128)),
+        Value::Int(day.year as u128),
+    ]
+}
+
+/// An array with one object per record, one record per line.
+fn to_json(records: &[Record]) -> String {
+    let lines = records
+        .iter()
+        .map(|record| {
+            let fields = COLUMNS
+                .iter()
+                .zip(record)
+                .map(|(column, value)| {
+                    let value = match value {
+                        Value::Null => "null".to_owned(),
+                        Value::Int(n) => n.to_string(),
+                        Value::Str(s) => json_string(s),
+                        Value::String(s) => json_string(s),
+                    };
+                    format!("\"{column}\": {value}")
+                })
+                .collect::<Vec<_>>();
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        "[]".to_owned()
+    } else {
+        format!("[\n{}\n]", lines.join(",\n"))
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            '\t' => result += "\\t",
+            c if c.is_control() => result += &format!("\\u{:04x}", c as u32),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// A header row followed by one row per record. Nulls are empty fields.
+fn to_csv(records: &[Record]) -> String {
+    let mut result = COLUMNS.join(",") + "\n";
+    for record in records {
+        let fields = record
+            .iter()
+            .map(|value| match value {
+                Value::Null => String::new(),
+                Value::Int(n) => n.to_string(),
+                Value::Str(s) => csv_field(s),
+                Value::String(s) => csv_field(s),
+            })
+            .collect::<Vec<_>>();
+        result += &fields.join(",");
+        result.push('\n');
+    }
+    result
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::TestCaseReport;
+
+    fn report() -> DayReport {
+        DayReport {
+            year: 2024,
+            day: 17,
+            parse_duration: None,
+            parts: vec![PartReport {
+                part: 1,
+                tests: vec![TestCaseReport {
+                    name: Some("small".to_owned()),
+                    status: TestStatus::Passed {
+                        output: "4,6,3".to_owned(),
+                    },
+                }],
+                real: RealStatus::Solved {
+                    output: "7,\"0\"".to_owned(),
+                    duration: Duration::from_micros(5),
+                    memory: None,
+                    verification: Verification::Unknown,
+                },
+                spans: vec![],
+                bench: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_export_json() {
+        assert_eq!(
+            export(Format::Json, &[report()]).unwrap(),
+            r#"[
+  {"day": 17, "part": 1, "test": "passed", "test_case": "small", "expected": "4,6,3", "actual": "4,6,3", "status": "solved", "answer": "7,\"0\"", "verification": "unknown", "duration_ns": 5000, "parse_duration_ns": null, "error": null, "peak_heap_bytes": null, "allocations": null, "year": 2024}
+]"#
+        );
+        assert_eq!(export(Format::Json, &[]).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_export_csv() {
+        assert_eq!(
+            export(Format::Csv, &[report()]).unwrap(),
+            "day,part,test,test_case,expected,actual,status,answer,verification,duration_ns,parse_duration_ns,error,peak_heap_bytes,allocations,year
+17,1,passed,small,\"4,6,3\",\"4,6,3\",solved,\"7,\"\"0\"\"\",unknown,5000,,,,,2024
+"
+        );
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+}
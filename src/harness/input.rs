@@ -1,111 +1,693 @@
+use crate::harness::{cancel, debug};
+use crate::regex;
 use crate::util::re;
-use crate::util::re::MatchTuple;
+use crate::util::re::{MatchTuple, Record};
 use regex::Regex;
-use std::fmt::Debug;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Display};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::{any, error};
 
+/// A line of the input, or part of one, along with where it starts so that
+/// parse errors can point at it.
 #[derive(Copy, Clone, Debug)]
-pub struct LineInput<'a>(&'a str);
+pub struct LineInput<'a> {
+    text: &'a str,
+    /// The line number, starting from 1.
+    line: usize,
+    /// The column of the first char, starting from 1.
+    column: usize,
+}
 
 impl<'a> LineInput<'a> {
     pub fn new(s: &'a str) -> Self {
-        Self(s)
+        Self::at(s, 1, 1)
+    }
+
+    fn at(text: &'a str, line: usize, column: usize) -> Self {
+        Self { text, line, column }
     }
 
     pub fn as_str(&self) -> &'a str {
-        self.0
+        self.text
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The part of this line at `s`, which must be a slice of it.
+    fn slice(&self, s: &'a str) -> Self {
+        let offset = s.as_ptr() as usize - self.text.as_ptr() as usize;
+        let column = self.column + self.text[..offset].chars().count();
+        Self::at(s, self.line, column)
     }
 
+    fn error(&self, expected: String, reason: Option<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            text: self.text.to_owned(),
+            expected,
+            reason,
+        }
+    }
+
+    pub fn try_single<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        self.text
+            .parse()
+            .map_err(|e| self.error(type_name::<T>(), Some(format!("{e:?}"))))
+    }
+
+    #[track_caller]
     pub fn single<T>(&self) -> T
     where
         T: FromStr,
         <T as FromStr>::Err: Debug,
     {
-        self.0.parse().unwrap()
+        or_panic(self.try_single())
     }
 
     pub fn chars(&self) -> Vec<char> {
-        self.0.chars().collect()
+        self.text.chars().collect()
     }
 
     pub fn bytes(&self) -> Vec<u8> {
-        self.0.bytes().collect()
+        self.text.bytes().collect()
     }
 
     pub fn digits(&self) -> Vec<usize> {
-        self.0.bytes().map(|b| (b - b'0') as usize).collect()
+        self.text.bytes().map(|b| (b - b'0') as usize).collect()
     }
 
+    pub fn try_split<T>(&self, pattern: &str) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        self.text
+            .split(pattern)
+            .map(|s| self.slice(s).try_single())
+            .collect()
+    }
+
+    #[track_caller]
     pub fn split<T>(&self, pattern: &str) -> Vec<T>
     where
         T: FromStr,
         <T as FromStr>::Err: Debug,
     {
-        self.0.split(pattern).map(|s| s.parse().unwrap()).collect()
+        or_panic(self.try_split(pattern))
     }
 
-    pub fn split_whitespace<T>(&self) -> Vec<T>
+    pub fn try_split_whitespace<T>(&self) -> Result<Vec<T>, ParseError>
     where
         T: FromStr,
         <T as FromStr>::Err: Debug,
     {
-        self.0
+        self.text
             .split_ascii_whitespace()
-            .map(|s| s.parse().unwrap())
+            .map(|s| self.slice(s).try_single())
+            .collect()
+    }
+
+    #[track_caller]
+    pub fn split_whitespace<T>(&self) -> Vec<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        or_panic(self.try_split_whitespace())
+    }
+
+    /// Every integer in the text, in order, e.g. `[-3, 4]` for `p=-3,4`. A `-`
+    /// right before a digit is read as a sign, unless it follows a letter or
+    /// digit, so that ranges such as `2-4` give `[2, 4]`.
+    pub fn try_ints<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        regex!(r"-?\d+")
+            .find_iter(self.text)
+            .map(|m| {
+                let follows_alphanumeric = self.text[..m.start()]
+                    .chars()
+                    .next_back()
+                    .is_some_and(char::is_alphanumeric);
+                let s = match m.as_str().strip_prefix('-') {
+                    Some(unsigned) if follows_alphanumeric => unsigned,
+                    _ => m.as_str(),
+                };
+                self.slice(s).try_single()
+            })
             .collect()
     }
 
+    #[track_caller]
+    pub fn ints<T>(&self) -> Vec<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        or_panic(self.try_ints())
+    }
+
+    /// Like `try_ints`, but fails unless there are exactly `N` integers.
+    pub fn try_ints_n<T, const N: usize>(&self) -> Result<[T; N], ParseError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        let ints = self.try_ints()?;
+        let count = ints.len();
+        ints.try_into()
+            .map_err(|_| self.error(format!("{N} integers"), Some(format!("found {count}"))))
+    }
+
+    #[track_caller]
+    pub fn ints_n<T, const N: usize>(&self) -> [T; N]
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        or_panic(self.try_ints_n())
+    }
+
+    pub fn try_split_once(&self, pattern: &str) -> Result<(Self, Self), ParseError> {
+        match self.text.split_once(pattern) {
+            Some((a, b)) => Ok((self.slice(a), self.slice(b))),
+            None => Err(self.error(format!("text containing {pattern:?}"), None)),
+        }
+    }
+
+    #[track_caller]
     pub fn split_once(&self, pattern: &str) -> (Self, Self) {
-        let (a, b) = self.0.split_once(pattern).unwrap();
-        (Self(a), Self(b))
+        or_panic(self.try_split_once(pattern))
     }
 
+    pub fn try_parse_with_regex<T>(&self, re: &Regex) -> Result<T, ParseError>
+    where
+        T: MatchTuple<'a>,
+    {
+        re::parse_with_regex(re, self.text).map_err(|e| {
+            let expected = format!("a match for /{re}/ as {}", type_name::<T>());
+            self.error(expected, Some(e.to_string()))
+        })
+    }
+
+    #[track_caller]
     pub fn parse_with_regex<T>(&self, re: &Regex) -> T
     where
         T: MatchTuple<'a>,
     {
-        re::parse_with_regex(re, self.0).unwrap()
+        or_panic(self.try_parse_with_regex(re))
+    }
+
+    /// Parses a struct declared with `regex_record!`.
+    pub fn try_record<T>(&self) -> Result<T, ParseError>
+    where
+        T: Record<'a>,
+    {
+        re::parse_record(self.text).map_err(|e| {
+            let expected = format!("a match for /{}/ as {}", T::regex(), type_name::<T>());
+            self.error(expected, Some(e.to_string()))
+        })
+    }
+
+    #[track_caller]
+    pub fn record<T>(&self) -> T
+    where
+        T: Record<'a>,
+    {
+        or_panic(self.try_record())
+    }
+}
+
+/// Converts `\r\n` line endings to `\n`, removes trailing whitespace from
+/// each line and removes trailing blank lines, so that inputs edited on any
+/// platform parse the same. Leaves a single final newline, unless the text is
+/// empty.
+pub fn normalize(text: &str) -> String {
+    let mut normalized = text
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Why part of the input couldn't be parsed, and where.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// The line number, starting from 1.
+    pub line: usize,
+    /// The column where `text` starts, starting from 1.
+    pub column: usize,
+    /// The text that couldn't be parsed.
+    pub text: String,
+    /// What the text should have been, such as the name of a type.
+    pub expected: String,
+    /// The underlying error, if there is one.
+    pub reason: Option<String>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Line {}, column {}: expected {}, found {:?}",
+            self.line, self.column, self.expected, self.text
+        )?;
+        if let Some(reason) = &self.reason {
+            write!(f, " ({reason})")?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// The name of a type without its module paths, e.g. `ArrayString<3>`.
+fn type_name<T>() -> String {
+    regex!(r"\b[a-z_][a-z0-9_]*::")
+        .replace_all(any::type_name::<T>(), "")
+        .into_owned()
+}
+
+/// Unwraps the result of a parse, panicking with the error's position.
+#[track_caller]
+fn or_panic<T>(result: Result<T, ParseError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => panic!("{e}"),
+    }
+}
+
+/// Named puzzle parameters, such as grid sizes or thresholds, whose values
+/// differ between the examples and the real input.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Params(BTreeMap<String, String>);
+
+static NO_PARAMS: Params = Params(BTreeMap::new());
+
+impl Params {
+    pub fn insert(&mut self, name: &str, value: &str) {
+        self.0.insert(name.to_owned(), value.to_owned());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+}
+
+/// The text and parameters of an input, owned so that they can be sent to the
+/// thread a solver runs on. Solvers read it through `raw`.
+#[derive(Clone, Debug)]
+pub struct OwnedInput {
+    text: Arc<str>,
+    params: Params,
+    /// The line number of the first line, starting from 1.
+    line: usize,
+}
+
+impl OwnedInput {
+    pub fn new(text: Arc<str>) -> Self {
+        Self::with_params(text, Params::default())
+    }
+
+    pub fn with_params(text: Arc<str>, params: Params) -> Self {
+        Self {
+            text,
+            params,
+            line: 1,
+        }
+    }
+
+    /// Like `RawInput::starting_at_line`.
+    pub fn starting_at_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+
+    pub fn raw(&self) -> RawInput<'_> {
+        RawInput::with_params(&self.text, &self.params).starting_at_line(self.line)
     }
 }
 
 #[derive(Copy, Clone, Debug)]
-pub struct RawInput<'a>(&'a str);
+pub struct RawInput<'a> {
+    text: &'a str,
+    params: &'a Params,
+    /// The line number of the first line, starting from 1.
+    line: usize,
+}
 
 impl<'a> RawInput<'a> {
     pub fn new(s: &'a str) -> Self {
-        Self(s)
+        Self::with_params(s, &NO_PARAMS)
+    }
+
+    pub fn with_params(s: &'a str, params: &'a Params) -> Self {
+        Self {
+            text: s,
+            params,
+            line: 1,
+        }
+    }
+
+    /// Numbers the lines from `line` in parse errors, for inputs that are
+    /// part of a larger file, such as the examples in a test-input file.
+    pub fn starting_at_line(self, line: usize) -> Self {
+        Self { line, ..self }
     }
 
     pub fn as_str(&self) -> &'a str {
-        self.0
+        self.text
+    }
+
+    /// Whether the solver has exceeded its time limit and should stop.
+    pub fn is_cancelled(&self) -> bool {
+        cancel::is_cancelled()
+    }
+
+    /// Stops the solver if it has exceeded its time limit. Call this from
+    /// loops that might not terminate.
+    pub fn check_cancelled(&self) {
+        cancel::check_cancelled()
+    }
+
+    /// Writes a diagnostic message, which is only shown with `--verbose`.
+    pub fn debug(&self, message: impl Display) {
+        debug::log(message)
+    }
+
+    /// Whether debug messages are shown, for skipping the work of building
+    /// them when they aren't.
+    pub fn is_debugging(&self) -> bool {
+        debug::is_enabled()
+    }
+
+    /// Returns the named parameter, or `default` if the input doesn't set it.
+    /// The real input never sets parameters, so `default` should be the value
+    /// for the real puzzle.
+    pub fn param<T>(&self, name: &str, default: T) -> T
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        match self.params.get(name) {
+            Some(value) => value
+                .parse()
+                .unwrap_or_else(|e| panic!("Invalid value for param {name}: {value} ({e:?})")),
+            None => default,
+        }
+    }
+
+    /// The part of this input at `text`, which must be a slice of it.
+    fn with_text(&self, text: &'a str) -> Self {
+        let offset = text.as_ptr() as usize - self.text.as_ptr() as usize;
+        let line = self.line + self.text[..offset].matches('\n').count();
+        Self::with_params(text, self.params).starting_at_line(line)
+    }
+
+    /// The lines of the input, numbered for parse errors.
+    fn lines(&self) -> impl Iterator<Item = LineInput<'a>> + 'a {
+        let first_line = self.line;
+        self.text
+            .lines()
+            .enumerate()
+            .map(move |(i, line)| LineInput::at(line, first_line + i, 1))
     }
 
     pub fn single_line<F, T>(&self, f: F) -> T
     where
         F: Fn(LineInput) -> T,
     {
-        let line = self.0.lines().next().unwrap();
-        f(LineInput(line))
+        let line = self.lines().next().expect("The input is empty.");
+        f(line)
     }
 
-    pub fn per_line<'b, F, T>(&self, f: F) -> impl Iterator<Item = T> + 'a
+    /// Every integer in the input, in order, as with `LineInput::try_ints`.
+    pub fn try_ints<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        let mut ints = vec![];
+        for line in self.lines() {
+            ints.extend(line.try_ints()?);
+        }
+        Ok(ints)
+    }
+
+    #[track_caller]
+    pub fn ints<T>(&self) -> Vec<T>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        or_panic(self.try_ints())
+    }
+
+    /// Like `try_ints`, but fails unless there are exactly `N` integers.
+    pub fn try_ints_n<T, const N: usize>(&self) -> Result<[T; N], ParseError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        let ints = self.try_ints()?;
+        let count = ints.len();
+        ints.try_into().map_err(|_| ParseError {
+            line: self.line,
+            column: 1,
+            text: self.text.to_owned(),
+            expected: format!("{N} integers"),
+            reason: Some(format!("found {count}")),
+        })
+    }
+
+    #[track_caller]
+    pub fn ints_n<T, const N: usize>(&self) -> [T; N]
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        or_panic(self.try_ints_n())
+    }
+
+    /// Calls `f` on each line. Parse errors from `LineInput`'s methods report
+    /// the line they happened on.
+    pub fn per_line<F, T>(&self, f: F) -> impl Iterator<Item = T> + 'a
     where
         F: Fn(LineInput) -> T + 'static,
     {
-        self.0.lines().map(move |line| f(LineInput(line)))
+        self.lines().map(f)
+    }
+
+    /// Like `per_line`, but stops at the first line that `f` fails to parse.
+    pub fn try_per_line<F, T>(&self, f: F) -> Result<Vec<T>, ParseError>
+    where
+        F: Fn(LineInput<'a>) -> Result<T, ParseError>,
+    {
+        self.lines().map(f).collect()
     }
 
     pub fn grouped_lines<F, T>(&self, f: F) -> impl Iterator<Item = Vec<T>> + 'a
     where
         F: Fn(LineInput) -> T + 'static,
     {
-        self.0
-            .split("\n\n")
-            .map(move |group| group.lines().map(|line| f(LineInput(line))).collect())
+        self.sections()
+            .into_iter()
+            .map(move |section| section.lines().map(&f).collect())
+    }
+
+    /// The blocks of lines separated by blank lines. Any number of blank lines
+    /// separate two sections, and leading and trailing ones are ignored.
+    /// Lines may end in `\r\n`, and lines with only whitespace count as blank.
+    pub fn sections(&self) -> Vec<Self> {
+        let mut sections = vec![];
+        // The byte range of the current section's lines.
+        let mut current: Option<(usize, usize)> = None;
+        let mut offset = 0;
+        for line in self.text.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                if let Some((start, end)) = current.take() {
+                    sections.push(self.with_text(&self.text[start..end]));
+                }
+            } else {
+                let end = offset + line.trim_end_matches(['\n', '\r']).len();
+                current = Some((current.map_or(offset, |(start, _)| start), end));
+            }
+            offset += line.len();
+        }
+        if let Some((start, end)) = current {
+            sections.push(self.with_text(&self.text[start..end]));
+        }
+        sections
+    }
+
+    /// Like `sections`, but fails unless there are exactly `N` sections.
+    pub fn try_sections_n<const N: usize>(&self) -> Result<[Self; N], ParseError> {
+        let sections = self.sections();
+        let count = sections.len();
+        sections.try_into().map_err(|_| ParseError {
+            line: self.line,
+            column: 1,
+            text: self.text.to_owned(),
+            expected: format!("{N} sections separated by blank lines"),
+            reason: Some(format!("found {count}")),
+        })
+    }
+
+    #[track_caller]
+    pub fn sections_n<const N: usize>(&self) -> [Self; N] {
+        or_panic(self.try_sections_n())
     }
 
     pub fn split_once_on_empty_line(&self) -> (Self, Self) {
-        let (a, b) = self.0.split_once("\n\n").unwrap();
-        (Self(a), Self(b))
+        let (a, b) = self.text.split_once("\n\n").unwrap();
+        (self.with_text(a), self.with_text(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_split() {
+        let line = LineInput::new("3 x 5");
+        assert_eq!(line.try_split::<String>(" ").unwrap(), ["3", "x", "5"]);
+        assert_eq!(
+            line.try_split_whitespace::<usize>(),
+            Err(ParseError {
+                line: 1,
+                column: 3,
+                text: "x".to_owned(),
+                expected: "usize".to_owned(),
+                reason: Some("ParseIntError { kind: InvalidDigit }".to_owned()),
+            })
+        );
+        let (a, b) = line.try_split_once(" x ").unwrap();
+        assert_eq!((a.as_str(), b.as_str(), b.column()), ("3", "5", 5));
+        assert_eq!(b.try_single::<u8>(), Ok(5));
+        assert_eq!(
+            line.try_split_once(",").unwrap_err().expected,
+            "text containing \",\""
+        );
+    }
+
+    #[test]
+    fn test_try_parse_with_regex() {
+        let line = LineInput::new("p=1,2");
+        let re = regex!(r"p=(\d+),(\d+)");
+        assert_eq!(line.try_parse_with_regex::<(u8, u8)>(re), Ok((1, 2)));
+        let error = LineInput::new("p=1").try_parse_with_regex::<(u8, u8)>(re);
+        assert_eq!(
+            error.unwrap_err().expected,
+            r"a match for /p=(\d+),(\d+)/ as (u8, u8)"
+        );
+    }
+
+    crate::regex_record! {
+        #[derive(Debug)]
+        struct Button = r"^Button (?<name>\w): X\+(?<x>\d+), Y\+(?<y>\d+)$" {
+            name: char,
+            x: usize,
+            y: usize,
+        }
+    }
+
+    #[test]
+    fn test_try_record() {
+        let button = LineInput::new("Button A: X+94, Y+34").record::<Button>();
+        assert_eq!((button.name, button.x, button.y), ('A', 94, 34));
+        let error = LineInput::new("Button AB: X+94, Y+34").try_record::<Button>();
+        assert_eq!(
+            error.unwrap_err().reason.unwrap(),
+            "Regex did not match string."
+        );
+    }
+
+    #[test]
+    fn test_ints() {
+        let line = LineInput::new("p=-3,4 v=12,-5");
+        assert_eq!(line.ints::<isize>(), [-3, 4, 12, -5]);
+        assert_eq!(line.try_ints_n::<isize, 4>(), Ok([-3, 4, 12, -5]));
+        let error = line.try_ints_n::<isize, 2>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 1, column 1: expected 2 integers, found \"p=-3,4 v=12,-5\" (found 4)"
+        );
+        assert_eq!(line.try_ints::<usize>().unwrap_err().column, 3);
+        assert_eq!(LineInput::new("2-4,6-8").ints::<usize>(), [2, 4, 6, 8]);
+        assert_eq!(LineInput::new("x-1 (-2) a -3").ints::<isize>(), [1, -2, -3]);
+        let input = RawInput::new("Register A: 729\n\nProgram: 0,1,5\n");
+        assert_eq!(input.ints::<usize>(), [729, 0, 1, 5]);
+        let (_, program) = input.split_once_on_empty_line();
+        assert_eq!(program.try_ints_n::<u8, 2>().unwrap_err().line, 3);
+        assert_eq!(RawInput::new("").ints::<u8>(), []);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("a \r\n\tb\t\r\n\r\n\r\nc\r\n\r\n  \n"),
+            "a\n\tb\n\n\nc\n"
+        );
+        assert_eq!(normalize("a"), "a\n");
+        assert_eq!(normalize("\n \n"), "");
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = RawInput::new("\na\r\nb\r\n\r\n \n\nc\n\n\n");
+        let sections = input.sections();
+        let texts = sections.iter().map(RawInput::as_str).collect::<Vec<_>>();
+        assert_eq!(texts, ["a\r\nb", "c"]);
+        assert_eq!(sections[1].single_line(|line| line.line()), 7);
+        let [first, second] = input.sections_n();
+        assert_eq!((first.as_str(), second.as_str()), ("a\r\nb", "c"));
+        let error = input.try_sections_n::<3>().unwrap_err();
+        assert_eq!(
+            (error.expected.as_str(), error.reason.unwrap().as_str()),
+            ("3 sections separated by blank lines", "found 2")
+        );
+        assert!(RawInput::new("\n\n").sections().is_empty());
+    }
+
+    #[test]
+    fn test_line_numbers() {
+        let input = RawInput::new("1\n2\n\n3\nx\n");
+        let (_, second) = input.split_once_on_empty_line();
+        let error = second.try_per_line(|line| line.try_single::<usize>());
+        assert_eq!(error.unwrap_err().line, 5);
+        let lines = input.grouped_lines(|line| line.line()).collect::<Vec<_>>();
+        assert_eq!(lines, vec![vec![1, 2], vec![4, 5]]);
+        let input = input.starting_at_line(10);
+        assert_eq!(input.single_line(|line| line.line()), 10);
+        assert_eq!(
+            ParseError {
+                reason: None,
+                ..input
+                    .try_per_line(|line| line.try_single::<usize>())
+                    .unwrap_err()
+            }
+            .to_string(),
+            "Line 12, column 1: expected usize, found \"\""
+        );
     }
 }
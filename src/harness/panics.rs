@@ -1,47 +1,89 @@
+use crate::harness::cancel::Cancelled;
 use std::any::Any;
+use std::cell::{Cell, RefCell};
 use std::panic;
 use std::panic::UnwindSafe;
-use std::sync::{Arc, Mutex};
+use std::sync::Once;
 
-/// Calls the provided function and wraps its result in `Some`. If it panics due
-/// to a `todo!()` call, then returns `None`. Any other panic proceeds normally.
-pub fn catching_todo<F, O>(f: F) -> Option<O>
+/// Why a solver didn't produce an output.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Failure {
+    /// The solver called `todo!()`.
+    NotImplemented,
+    Panicked(Panic),
+    /// The solver exceeded its time limit.
+    TimedOut,
+}
+
+/// A panic other than `todo!()`, with its message and where it happened.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Panic {
+    pub message: String,
+    /// The `file:line:column` of the panic, if known.
+    pub location: Option<String>,
+}
+
+impl std::fmt::Display for Panic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    /// Whether this thread is inside `catching`, in which case panics are
+    /// recorded instead of printed.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Calls the provided function and wraps its result in `Ok`. If it panics,
+/// returns the panic as a `Failure` instead, without printing it. Unwinding
+/// after a cancellation is reported as a timeout.
+pub fn catching<F, O>(f: F) -> Result<O, Failure>
 where
-    F: UnwindSafe + Fn() -> O,
+    F: UnwindSafe + FnOnce() -> O,
 {
-    let old_hook = Arc::new(Mutex::new(Some(panic::take_hook())));
-    panic::set_hook({
-        let old_hook = Arc::clone(&old_hook);
-        Box::new(move |info| {
-            if !is_todo(info.payload()) {
-                if let Some(hook) = &*old_hook.lock().unwrap() {
-                    hook(info)
-                }
-            }
-        })
-    });
+    install_hook();
+    let was_catching = CATCHING.replace(true);
     let result = panic::catch_unwind(f);
-    let old_hook = old_hook.lock().unwrap().take().unwrap();
-    panic::set_hook(old_hook);
-    match result {
-        Ok(o) => Some(o),
-        Err(error) => {
-            if is_todo(&*error) {
-                None
+    CATCHING.set(was_catching);
+    let location = LOCATION.take();
+    result.map_err(|error| match message(&*error) {
+        _ if error.is::<Cancelled>() => Failure::TimedOut,
+        Some(message) if message.starts_with("not yet implemented") => Failure::NotImplemented,
+        message => Failure::Panicked(Panic {
+            message: message.unwrap_or("Box<dyn Any>").to_owned(),
+            location,
+        }),
+    })
+}
+
+/// Wraps the existing panic hook, once, so that it's skipped for panics caught
+/// by `catching`. The flag is per thread, so panics on other threads are
+/// still printed.
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let old_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                let location = info.location().map(ToString::to_string);
+                LOCATION.set(location);
             } else {
-                panic::resume_unwind(error)
+                old_hook(info)
             }
-        }
-    }
+        }));
+    });
 }
 
-fn is_todo(error: &dyn Any) -> bool {
+fn message(error: &dyn Any) -> Option<&str> {
     if let Some(&s) = error.downcast_ref::<&str>() {
-        s.starts_with("not yet implemented")
-    } else if let Some(s) = error.downcast_ref::<String>() {
-        s.starts_with("not yet implemented")
+        Some(s)
     } else {
-        false
+        error.downcast_ref::<String>().map(String::as_str)
     }
 }
 
@@ -50,26 +92,42 @@ mod tests {
     use super::*;
 
     #[test]
-    fn catching_todo_on_no_panic() {
-        let result = catching_todo(|| 42);
-        assert_eq!(result, Some(42));
+    fn catching_on_no_panic() {
+        let result = catching(|| 42);
+        assert_eq!(result, Ok(42));
     }
 
     #[test]
-    fn catching_todo_catches_todo() {
-        let result = catching_todo(|| todo!());
-        assert_eq!(result, None);
+    fn catching_catches_todo() {
+        let result = catching(|| todo!());
+        assert_eq!(result, Err::<(), _>(Failure::NotImplemented));
     }
 
     #[test]
-    fn catching_todo_catches_todo_with_message() {
-        let result = catching_todo(|| todo!("stuff"));
-        assert_eq!(result, None);
+    fn catching_catches_todo_with_message() {
+        let result = catching(|| todo!("stuff"));
+        assert_eq!(result, Err::<(), _>(Failure::NotImplemented));
     }
 
     #[test]
-    #[should_panic]
-    fn catching_todo_allows_other_panics() {
-        catching_todo(|| unimplemented!());
+    fn catching_catches_other_panics() {
+        let result = catching(|| "x".parse::<usize>().unwrap());
+        let Err(Failure::Panicked(panic)) = result else {
+            panic!("Expected a panic, got {result:?}");
+        };
+        assert_eq!(
+            panic.message,
+            "called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }"
+        );
+        assert!(panic
+            .location
+            .unwrap()
+            .starts_with("src/harness/panics.rs:"));
+
+        let result = catching(|| panic!("bad input: {}", 7));
+        let Err(Failure::Panicked(panic)) = result else {
+            panic!("Expected a panic, got {result:?}");
+        };
+        assert_eq!(panic.message, "bad input: 7");
     }
 }
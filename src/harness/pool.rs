@@ -0,0 +1,80 @@
+use crate::harness::{Day, DayResult, Options};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Matches the main thread's stack, which some of the recursive solvers need.
+pub const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Runs the days on up to `jobs` worker threads. Each day, including the
+/// timing of its parts, runs entirely on one worker. The reports are returned
+/// in the same order as `days`.
+pub fn run(days: &[&Day], options: &Options, jobs: usize) -> Vec<DayResult> {
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(vec![None; days.len()]);
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(day) = days.get(i) else {
+                        break;
+                    };
+                    let report = (day.solve)(options);
+                    reports.lock().unwrap()[i] = Some(report);
+                })
+                .unwrap();
+        }
+    });
+    reports
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(Option::unwrap)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::DayReport;
+    use std::time::Duration;
+
+    fn report(day: usize) -> DayReport {
+        DayReport {
+            year: 2024,
+            day,
+            parse_duration: None,
+            parts: vec![],
+        }
+    }
+
+    #[test]
+    fn test_run_keeps_order() {
+        let solves: [fn(&Options) -> DayResult; 4] = [
+            |_| {
+                thread::sleep(Duration::from_millis(20));
+                Ok(report(1))
+            },
+            |_| Ok(report(2)),
+            |_| Err("Input file not found".to_owned()),
+            |_| Ok(report(4)),
+        ];
+        let days = solves
+            .into_iter()
+            .enumerate()
+            .map(|(i, solve)| Day {
+                number: i + 1,
+                solve,
+            })
+            .collect::<Vec<_>>();
+        let days = days.iter().collect::<Vec<_>>();
+        let reports = run(&days, &Options::default(), 3);
+        let numbers = reports
+            .iter()
+            .map(|report| report.as_ref().ok().map(|report| report.day))
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![Some(1), Some(2), None, Some(4)]);
+    }
+}
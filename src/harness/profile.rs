@@ -0,0 +1,205 @@
+//! Named spans that solvers can put around their hot sections, e.g.
+//!
+//! ```ignore
+//! let _span = harness::span("bfs");
+//! ```
+//!
+//! The harness collects the spans of each part's run on the real input and
+//! reports the time spent in each one, aggregated by name under its parent
+//! span. Outside of a collection, such as on the test inputs, spans do
+//! nothing.
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static PROFILE: RefCell<Option<Profile>> = const { RefCell::new(None) };
+}
+
+/// The total time spent in a span across all of its calls, and the spans
+/// opened inside it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpanReport {
+    pub name: &'static str,
+    pub duration: Duration,
+    pub count: usize,
+    pub children: Vec<SpanReport>,
+}
+
+/// The spans collected so far. Node 0 is the root, which stands for the whole
+/// run and isn't reported.
+struct Profile {
+    nodes: Vec<Node>,
+    /// The open spans, innermost last, starting with the root.
+    open: Vec<usize>,
+}
+
+struct Node {
+    name: &'static str,
+    duration: Duration,
+    count: usize,
+    children: Vec<usize>,
+}
+
+impl Profile {
+    fn new() -> Self {
+        Profile {
+            nodes: vec![Node::new("")],
+            open: vec![0],
+        }
+    }
+
+    fn enter(&mut self, name: &'static str) {
+        let parent = *self.open.last().unwrap();
+        let existing = self.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].name == name);
+        let node = existing.unwrap_or_else(|| {
+            self.nodes.push(Node::new(name));
+            let node = self.nodes.len() - 1;
+            self.nodes[parent].children.push(node);
+            node
+        });
+        self.open.push(node);
+    }
+
+    fn exit(&mut self, duration: Duration) {
+        // Never pops the root, even if a span outlived the collection it
+        // started in.
+        if self.open.len() > 1 {
+            let node = &mut self.nodes[self.open.pop().unwrap()];
+            node.duration += duration;
+            node.count += 1;
+        }
+    }
+
+    fn report(&self, node: usize) -> Vec<SpanReport> {
+        self.nodes[node]
+            .children
+            .iter()
+            .map(|&child| {
+                let Node {
+                    name,
+                    duration,
+                    count,
+                    ..
+                } = self.nodes[child];
+                SpanReport {
+                    name,
+                    duration,
+                    count,
+                    children: self.report(child),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Node {
+    fn new(name: &'static str) -> Self {
+        Node {
+            name,
+            duration: Duration::ZERO,
+            count: 0,
+            children: vec![],
+        }
+    }
+}
+
+/// Times the code up to the end of the returned guard's scope as part of the
+/// span `name`, nested under the span that's currently open, if any.
+pub fn span(name: &'static str) -> Span {
+    let collecting = PROFILE.with_borrow_mut(|profile| match profile {
+        Some(profile) => {
+            profile.enter(name);
+            true
+        }
+        None => false,
+    });
+    Span {
+        start_time: collecting.then(Instant::now),
+    }
+}
+
+/// Closes its span when dropped.
+#[must_use = "the span is closed as soon as it's dropped"]
+pub struct Span {
+    /// `None` if spans weren't being collected when it was opened.
+    start_time: Option<Instant>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(start_time) = self.start_time {
+            let duration = start_time.elapsed();
+            PROFILE.with_borrow_mut(|profile| {
+                if let Some(profile) = profile {
+                    profile.exit(duration);
+                }
+            });
+        }
+    }
+}
+
+/// Calls `f`, collecting the spans it opens on this thread. The spans are
+/// discarded if `f` panics.
+pub fn collect<F, O>(f: F) -> (O, Vec<SpanReport>)
+where
+    F: FnOnce() -> O,
+{
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            PROFILE.set(None);
+        }
+    }
+
+    PROFILE.set(Some(Profile::new()));
+    let _reset = Reset;
+    let output = f();
+    let spans = PROFILE.with_borrow(|profile| profile.as_ref().unwrap().report(0));
+    (output, spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summarize(spans: &[SpanReport]) -> Vec<(&'static str, usize, usize)> {
+        spans
+            .iter()
+            .map(|span| (span.name, span.count, span.children.len()))
+            .collect()
+    }
+
+    #[test]
+    fn test_collect() {
+        let (output, spans) = collect(|| {
+            for _ in 0..3 {
+                let _outer = span("outer");
+                let _inner = span("inner");
+            }
+            let _inner = span("inner");
+            7
+        });
+        assert_eq!(output, 7);
+        assert_eq!(summarize(&spans), vec![("outer", 3, 1), ("inner", 1, 0)]);
+        assert_eq!(summarize(&spans[0].children), vec![("inner", 3, 0)]);
+        assert!(spans[0].duration >= spans[0].children[0].duration);
+    }
+
+    #[test]
+    fn test_span_outside_collect() {
+        let outside = span("outside");
+        let (_, spans) = collect(|| {
+            let _inside = span("inside");
+        });
+        drop(outside);
+        assert_eq!(summarize(&spans), vec![("inside", 1, 0)]);
+        let (_, spans) = collect(|| {});
+        assert!(spans.is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+use crate::harness::answers::Verification;
+use crate::harness::bench::{BenchReport, BenchStats};
+use crate::harness::profile::SpanReport;
+use crate::harness::{
+    DayReport, Mode, Options, PartReport, RealStatus, TestCaseReport, TestStatus,
+};
+use std::time::Duration;
+
+/// Prints the results of a single day as each part's test and real output,
+/// preceded by the parse duration for days with a separate parse step.
+pub fn print_day(options: &Options, report: &DayReport) {
+    if let (Some(duration), false) = (report.parse_duration, options.quiet) {
+        println!("Parse duration: {duration:.2?}");
+        println!();
+    }
+    for (i, part) in report.parts.iter().enumerate() {
+        if options.quiet {
+            print_part_quiet(options, part);
+        } else {
+            if i > 0 {
+                println!();
+            }
+            print_part(options, part);
+        }
+    }
+}
+
+fn print_part(options: &Options, report: &PartReport) {
+    let part = report.part;
+    if report.tests.is_empty() && options.mode == Mode::TestOnly {
+        println!("Part {part} has no expected test output.");
+    }
+    for test in &report.tests {
+        print_test_case(part, test);
+    }
+    match &report.real {
+        RealStatus::Solved {
+            output,
+            duration,
+            memory,
+            verification,
+        } => {
+            let label = format!("Part {part} output: ");
+            match verification {
+                Verification::Verified => print_labeled(&label, output, " ✅"),
+                Verification::Wrong { accepted } => {
+                    let width = label.chars().count();
+                    print_labeled(&label, output, " ❌");
+                    print_labeled(&format!("{:>width$}", "Accepted: "), accepted, "");
+                }
+                Verification::Unknown => print_labeled(&label, output, ""),
+            }
+            let mut stats = match &report.bench {
+                Some(bench) => format_bench(bench),
+                None => format!("Duration: {duration:.2?}"),
+            };
+            if let Some(memory) = memory {
+                stats += &format!(
+                    ", peak heap: {}, allocations: {}",
+                    format_bytes(memory.peak_bytes),
+                    memory.allocations
+                );
+            }
+            println!("   ↑ {stats}");
+            print_spans(&report.spans, *duration, 1);
+        }
+        RealStatus::NotImplemented => println!("Part {part} not implemented."),
+        RealStatus::Panicked(panic) => println!("Part {part} {panic} 💥"),
+        RealStatus::TimedOut => println!("Part {part} timed out. ⏰"),
+        RealStatus::Skipped => {}
+    }
+}
+
+fn print_part_quiet(options: &Options, report: &PartReport) {
+    for test in &report.tests {
+        match &test.status {
+            TestStatus::Passed { output } if options.mode == Mode::TestOnly => println!("{output}"),
+            TestStatus::Failed { .. } | TestStatus::Panicked(_) | TestStatus::TimedOut => {
+                print_test_case(report.part, test)
+            }
+            _ => {}
+        }
+    }
+    match &report.real {
+        RealStatus::Solved { output, .. } => println!("{output}"),
+        RealStatus::Panicked(panic) => println!("Part {} {panic} 💥", report.part),
+        RealStatus::TimedOut => println!("Part {} timed out. ⏰", report.part),
+        _ => {}
+    }
+}
+
+fn print_test_case(part: usize, test: &TestCaseReport) {
+    let label = match &test.name {
+        Some(name) => format!("Part {part} test output ({name}): "),
+        None => format!("Part {part} test output: "),
+    };
+    match &test.status {
+        TestStatus::Passed { output } => print_labeled(&label, output, " ✅"),
+        TestStatus::Failed { output, expected } => {
+            let width = label.chars().count();
+            print_labeled(&label, output, " ❌");
+            print_labeled(&format!("{:>width$}", "Expected: "), expected, "");
+        }
+        TestStatus::NotImplemented => println!("Part {part} not implemented."),
+        TestStatus::Panicked(panic) => match &test.name {
+            Some(name) => println!("Part {part} test ({name}) {panic} 💥"),
+            None => println!("Part {part} test {panic} 💥"),
+        },
+        TestStatus::TimedOut => match &test.name {
+            Some(name) => println!("Part {part} test ({name}) timed out. ⏰"),
+            None => println!("Part {part} test timed out. ⏰"),
+        },
+    }
+}
+
+/// Prints a tree of spans, each with its total time, its share of the part's
+/// duration and how many times it was entered.
+fn print_spans(spans: &[SpanReport], total: Duration, depth: usize) {
+    let width = spans
+        .iter()
+        .map(|span| span.name.chars().count())
+        .max()
+        .unwrap_or_default();
+    for span in spans {
+        let share = 100.0 * span.duration.as_secs_f64() / total.as_secs_f64().max(f64::EPSILON);
+        let calls = if span.count == 1 { "call" } else { "calls" };
+        println!(
+            "{:indent$}{:width$}  {:>9}  {share:>5.1}%  {} {calls}",
+            "",
+            span.name,
+            format!("{:.2?}", span.duration),
+            span.count,
+            indent = 3 + 2 * depth,
+        );
+        print_spans(&span.children, total, depth + 1);
+    }
+}
+
+/// Prints a value after its label, or on the lines below it if the value has
+/// multiple lines, such as an answer drawn in ASCII art.
+fn print_labeled(label: &str, value: &str, mark: &str) {
+    if value.contains('\n') {
+        println!("{}{mark}", label.trim_end());
+        println!("{value}");
+    } else {
+        println!("{label}{value}{mark}");
+    }
+}
+
+/// Prints one row per part with its test status, answer, answer verification and
+/// duration, followed by the total runtime of the real inputs. Benchmarked parts
+/// show their median duration. With the `alloc-stats` feature, rows also show
+/// the heap usage. Days with a separate parse step get an extra
+/// row for it.
+pub fn print_summary(reports: &[DayReport]) {
+    let part_rows = reports
+        .iter()
+        .flat_map(|day| day.parts.iter().map(move |part| (day.day, part)))
+        .map(|(day, part)| {
+            let answer = match &part.real {
+                RealStatus::Solved { output, .. } if output.contains('\n') => {
+                    format!("({} lines)", output.lines().count())
+                }
+                RealStatus::Solved { output, .. } => output.clone(),
+                RealStatus::NotImplemented => "not implemented".to_owned(),
+                RealStatus::Panicked(_) => "panicked".to_owned(),
+                RealStatus::TimedOut => "timed out".to_owned(),
+                RealStatus::Skipped => match part.test_summary() {
+                    Some(TestStatus::NotImplemented) => "not implemented".to_owned(),
+                    Some(TestStatus::Panicked(_)) => "panicked".to_owned(),
+                    Some(TestStatus::TimedOut) => "timed out".to_owned(),
+                    _ => "-".to_owned(),
+                },
+            };
+            let duration = match (&part.real, &part.bench) {
+                (_, Some(bench)) => format!("{:.2?}", bench.stats.median),
+                (RealStatus::Solved { duration, .. }, None) => format!("{duration:.2?}"),
+                _ => "-".to_owned(),
+            };
+            let verification = match &part.real {
+                RealStatus::Solved { verification, .. } => verification_symbol(verification),
+                _ => "--",
+            };
+            let memory = match &part.real {
+                RealStatus::Solved {
+                    memory: Some(memory),
+                    ..
+                } => format!(
+                    "  {:>9}  {:>11}",
+                    format_bytes(memory.peak_bytes),
+                    memory.allocations
+                ),
+                _ if cfg!(feature = "alloc-stats") => format!("  {:>9}  {:>11}", "-", "-"),
+                _ => String::new(),
+            };
+            (
+                day,
+                part.part.to_string(),
+                test_symbol(part.test_summary()),
+                verification,
+                answer,
+                duration,
+                memory,
+            )
+        })
+        .collect::<Vec<_>>();
+    let verified_count = part_rows.iter().filter(|row| row.3 == "✅").count();
+    let part_count = part_rows.len();
+    let mut rows = reports
+        .iter()
+        .filter_map(|day| {
+            let duration = day.parse_duration?;
+            let row = (
+                day.day,
+                "parse".to_owned(),
+                "--",
+                "--",
+                String::new(),
+                format!("{duration:.2?}"),
+                String::new(),
+            );
+            Some(row)
+        })
+        .chain(part_rows)
+        .collect::<Vec<_>>();
+    // Stable, so each parse row stays ahead of its day's parts.
+    rows.sort_by_key(|row| row.0);
+    let answer_width = rows
+        .iter()
+        .map(|row| row.4.chars().count())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap();
+    let memory_header = if cfg!(feature = "alloc-stats") {
+        "  Peak heap  Allocations"
+    } else {
+        ""
+    };
+    println!(
+        "Day   Part  Test  Real  {:answer_width$}  Duration{memory_header}",
+        "Answer"
+    );
+    for (day, part, test, verification, answer, duration, memory) in rows {
+        println!(
+            "{day:>3}  {part:>5}   {test}    {verification}   {answer:answer_width$}  {duration:>8}{memory}"
+        );
+    }
+    println!();
+    println!("Verified answers: {verified_count}/{part_count}");
+    println!("Total runtime: {:.2?}", total_duration(reports));
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.2} {}", UNITS[unit])
+    }
+}
+
+fn format_bench(bench: &BenchReport) -> String {
+    let BenchStats {
+        runs,
+        min,
+        median,
+        mean,
+        std_dev,
+    } = bench.stats;
+    let mut s = format!(
+        "Min: {min:.2?}, median: {median:.2?}, mean: {mean:.2?}, std dev: {std_dev:.2?} ({runs} runs)"
+    );
+    if let Some(change) = bench.change() {
+        s += &format!(", {change:+.1}% vs baseline");
+    }
+    s
+}
+
+/// Uses two-column symbols throughout so that the table stays aligned.
+fn test_symbol(status: Option<&TestStatus>) -> &'static str {
+    match status {
+        Some(TestStatus::Passed { .. }) => "✅",
+        Some(TestStatus::Failed { .. }) => "❌",
+        Some(TestStatus::NotImplemented) => "🚧",
+        Some(TestStatus::Panicked(_)) => "💥",
+        Some(TestStatus::TimedOut) => "⏰",
+        None => "--",
+    }
+}
+
+fn verification_symbol(verification: &Verification) -> &'static str {
+    match verification {
+        Verification::Verified => "✅",
+        Verification::Wrong { .. } => "❌",
+        Verification::Unknown => "--",
+    }
+}
+
+fn total_duration(reports: &[DayReport]) -> Duration {
+    let parse_duration = reports
+        .iter()
+        .filter_map(|day| day.parse_duration)
+        .sum::<Duration>();
+    let solve_duration = reports
+        .iter()
+        .flat_map(|day| &day.parts)
+        .filter_map(|part| match (&part.real, &part.bench) {
+            (_, Some(bench)) => Some(bench.stats.median),
+            (RealStatus::Solved { duration, .. }, None) => Some(*duration),
+            _ => None,
+        })
+        .sum::<Duration>();
+    parse_duration + solve_duration
+}
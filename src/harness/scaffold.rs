@@ -0,0 +1,219 @@
+use crate::harness::input_file;
+use crate::regex;
+use std::error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const YEAR_REGISTRY_PATH: &str = "src/years.rs";
+
+const DAY_TEMPLATE: &str = "\
+use crate::harness::input::RawInput;
+
+pub fn solve_part1(_input: RawInput) -> usize {
+    todo!()
+}
+
+pub fn solve_part2(_input: RawInput) -> usize {
+    todo!()
+}
+";
+
+const YEAR_TEMPLATE: &str = "\
+register_days! {
+    year = YEAR;
+}
+";
+
+const TEST_INPUT_TEMPLATE: &str = "\
+Part 1 expected:
+Part 2 expected:
+
+";
+
+/// Creates the module and test-input file for a new day and adds it to its
+/// year's registry, registering the year too if it's new. Fails without
+/// changing anything if any of them already has the day. Returns the paths
+/// that were written.
+pub fn new_day(year: usize, day: usize) -> Result<Vec<PathBuf>, Box<dyn error::Error>> {
+    let registry_path = PathBuf::from(format!("src/years/y{year}.rs"));
+    let module_path = PathBuf::from(format!("src/years/y{year}/day{day:02}.rs"));
+    let test_input_path = input_file(year, day, "test-input");
+    for path in [&module_path, &test_input_path] {
+        if path.exists() {
+            Err(format!("{} already exists.", path.display()))?
+        }
+    }
+    let is_new_year = !registry_path.exists();
+    let year_registry = if is_new_year {
+        let source = fs::read_to_string(YEAR_REGISTRY_PATH)?;
+        let module = format!("y{year}");
+        let source = register(&source, "register_years", &module, year)?;
+        Some(declare_module(&source, &module))
+    } else {
+        None
+    };
+    let registry = if is_new_year {
+        YEAR_TEMPLATE.replace("YEAR", &year.to_string())
+    } else {
+        fs::read_to_string(&registry_path)?
+    };
+    let module = format!("day{day:02}");
+    let registry = register(&registry, "register_days", &module, day)?;
+    let registry = declare_module(&registry, &module);
+    for path in [&module_path, &test_input_path] {
+        fs::create_dir_all(path.parent().unwrap())?;
+    }
+    create_new(&module_path, DAY_TEMPLATE)?;
+    create_new(&test_input_path, TEST_INPUT_TEMPLATE)?;
+    fs::write(&registry_path, registry)?;
+    let mut paths = vec![module_path, test_input_path, registry_path];
+    if let Some(year_registry) = year_registry {
+        fs::write(YEAR_REGISTRY_PATH, year_registry)?;
+        paths.push(PathBuf::from(YEAR_REGISTRY_PATH));
+    }
+    Ok(paths)
+}
+
+fn create_new(path: &Path, contents: &str) -> Result<(), Box<dyn error::Error>> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| format!("Failed to create {}: {e}", path.display()))?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+/// Adds a `pub mod` item for the module among the existing ones, keeping them
+/// sorted, or at the top if there are none. The modules are declared outside
+/// the registry macros so that rustfmt formats them.
+fn declare_module(source: &str, module: &str) -> String {
+    let item = format!("pub mod {module};\n");
+    let mut first = None;
+    let mut after_preceding = None;
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        if line.starts_with("pub mod ") {
+            first.get_or_insert(offset);
+            if line < item.as_str() {
+                after_preceding = Some(offset + line.len());
+            }
+        }
+        offset += line.len();
+    }
+    match after_preceding.or(first) {
+        Some(i) => [&source[..i], &item, &source[i..]].concat(),
+        None => format!("{item}\n{source}"),
+    }
+}
+
+/// Adds an entry of the form `module = number` to the invocation of the
+/// registry macro in the source, keeping the entries sorted by number.
+fn register(
+    source: &str,
+    macro_name: &str,
+    module: &str,
+    number: usize,
+) -> Result<String, Box<dyn error::Error>> {
+    let header = format!("{macro_name}! {{\n");
+    let start = source
+        .match_indices(&header)
+        .map(|(i, _)| i)
+        .find(|&i| i == 0 || source[..i].ends_with('\n'))
+        .ok_or_else(|| format!("Couldn't find the {macro_name}! invocation."))?;
+    let end = start
+        + source[start..]
+            .find("\n}")
+            .ok_or_else(|| format!("Couldn't find the end of the {macro_name}! invocation."))?
+        + 1;
+    // Skips the `year = N;` line of `register_days!`, which isn't an entry.
+    let entry_re = regex!(r"^ *([a-z]\w*) = (\d+)\b");
+    let mut insert_at = end;
+    let mut offset = start;
+    for line in source[start..end].split_inclusive('\n') {
+        if let Some(caps) = entry_re.captures(line).filter(|caps| &caps[1] != "year") {
+            let existing = caps[2].parse::<usize>()?;
+            if existing == number {
+                Err(format!("{module} is already registered."))?
+            }
+            if existing > number && insert_at == end {
+                insert_at = offset;
+            }
+        }
+        offset += line.len();
+    }
+    let entry = format!("    {module} = {number},\n");
+    Ok([&source[..insert_at], &entry, &source[insert_at..]].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+macro_rules! register_days {
+    () => {};
+}
+
+register_days! {
+    year = 2024;
+    day01 = 1,
+    day03 = 3 (parsed),
+}
+";
+
+    fn register_day(source: &str, day: usize) -> Result<String, Box<dyn error::Error>> {
+        register(source, "register_days", &format!("day{day:02}"), day)
+    }
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register_day(SOURCE, 2).unwrap(),
+            SOURCE.replace("    day03", "    day02 = 2,\n    day03")
+        );
+        assert_eq!(
+            register_day(SOURCE, 4).unwrap(),
+            SOURCE.replace("(parsed),\n", "(parsed),\n    day04 = 4,\n")
+        );
+        let new_year = YEAR_TEMPLATE.replace("YEAR", "2023");
+        assert_eq!(
+            declare_module(&new_year, "day01"),
+            format!("pub mod day01;\n\n{new_year}")
+        );
+        assert_eq!(
+            register_day(&new_year, 1).unwrap(),
+            "register_days! {\n    year = 2023;\n    day01 = 1,\n}\n"
+        );
+        let years = "register_years! {\n    y2024 = 2024,\n}\n";
+        assert_eq!(
+            register(years, "register_years", "y2023", 2023).unwrap(),
+            "register_years! {\n    y2023 = 2023,\n    y2024 = 2024,\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_declare_module() {
+        let source = "use x;\n\npub mod day01;\npub mod day03;\n\nregister_days! {}\n";
+        assert_eq!(
+            declare_module(source, "day02"),
+            source.replace("day01;\n", "day01;\npub mod day02;\n")
+        );
+        assert_eq!(
+            declare_module(source, "day04"),
+            source.replace("day03;\n", "day03;\npub mod day04;\n")
+        );
+        let source = "pub mod y2024;\n";
+        assert_eq!(
+            declare_module(source, "y2023"),
+            "pub mod y2023;\npub mod y2024;\n"
+        );
+    }
+
+    #[test]
+    fn test_register_existing_day() {
+        assert!(register_day(SOURCE, 3).is_err());
+        assert!(register_day("fn main() {}\n", 3).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::harness::input::{OwnedInput, RawInput};
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+/// A day's solution, split into a parse phase producing a typed input and the
+/// two parts, which solve from that input. The harness times the phases
+/// separately and parses only once when running both parts. Everything is
+/// owned and thread-safe, so that each phase can run on its own thread under
+/// a time limit.
+pub trait Solver: Send + Sync + 'static {
+    type Input: Send + Sync + 'static;
+    type Output1: Display + Send + 'static;
+    type Output2: Display + Send + 'static;
+
+    /// Whether `parse` does any work, so that it's worth reporting on its own.
+    const HAS_PARSE_PHASE: bool;
+
+    fn parse(&self, input: &OwnedInput) -> Self::Input;
+    fn solve_part1(&self, input: &Self::Input) -> Self::Output1;
+    fn solve_part2(&self, input: &Self::Input) -> Self::Output2;
+}
+
+/// A day whose parts each take the raw input and do their own parsing.
+pub struct SinglePhase<F1, F2> {
+    pub solve_part1: F1,
+    pub solve_part2: F2,
+}
+
+impl<F1, F2, O1, O2> Solver for SinglePhase<F1, F2>
+where
+    F1: Fn(RawInput) -> O1 + Send + Sync + 'static,
+    F2: Fn(RawInput) -> O2 + Send + Sync + 'static,
+    O1: Display + Send + 'static,
+    O2: Display + Send + 'static,
+{
+    type Input = OwnedInput;
+    type Output1 = O1;
+    type Output2 = O2;
+
+    const HAS_PARSE_PHASE: bool = false;
+
+    fn parse(&self, input: &OwnedInput) -> OwnedInput {
+        input.clone()
+    }
+
+    fn solve_part1(&self, input: &OwnedInput) -> O1 {
+        (self.solve_part1)(input.raw())
+    }
+
+    fn solve_part2(&self, input: &OwnedInput) -> O2 {
+        (self.solve_part2)(input.raw())
+    }
+}
+
+/// A day with a shared `parse` function whose output is passed to both parts.
+pub struct TwoPhase<P, F1, F2, I> {
+    pub parse: P,
+    pub solve_part1: F1,
+    pub solve_part2: F2,
+    pub input: PhantomData<I>,
+}
+
+impl<P, F1, F2, I, O1, O2> Solver for TwoPhase<P, F1, F2, I>
+where
+    P: Fn(RawInput) -> I + Send + Sync + 'static,
+    F1: Fn(&I) -> O1 + Send + Sync + 'static,
+    F2: Fn(&I) -> O2 + Send + Sync + 'static,
+    I: Send + Sync + 'static,
+    O1: Display + Send + 'static,
+    O2: Display + Send + 'static,
+{
+    type Input = I;
+    type Output1 = O1;
+    type Output2 = O2;
+
+    const HAS_PARSE_PHASE: bool = true;
+
+    fn parse(&self, input: &OwnedInput) -> I {
+        (self.parse)(input.raw())
+    }
+
+    fn solve_part1(&self, input: &I) -> O1 {
+        (self.solve_part1)(input)
+    }
+
+    fn solve_part2(&self, input: &I) -> O2 {
+        (self.solve_part2)(input)
+    }
+}
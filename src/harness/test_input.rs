@@ -0,0 +1,240 @@
+use crate::harness::answer;
+use crate::harness::input::Params;
+use crate::regex;
+use std::error;
+
+/// A single example from a test-input file, with the expected output of each
+/// part. An expected output is `None` if the puzzle doesn't give one for this
+/// example.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TestCase<'a> {
+    pub name: Option<&'a str>,
+    pub part1_expected: Option<String>,
+    pub part2_expected: Option<String>,
+    pub params: Params,
+    pub text: &'a str,
+}
+
+/// Parses a test-input file into its cases. A file with a single case looks
+/// like:
+///
+/// ```text
+/// Part 1 expected: 11
+/// Part 2 expected: 31
+///
+/// <example>
+/// ```
+///
+/// An expected output that spans multiple lines is written between `"""`
+/// lines, starting on the `Part N expected:` line. The expected outputs may be
+/// followed by `<name>: <value>` lines that set parameters for the example,
+/// e.g. `width: 11`. Further cases each start with a `== <name> ==` line
+/// followed by the same format. The first case may omit its name line.
+///
+/// All of these lines may end with `\r\n`, even with `--strict-input`, in
+/// which case the text of each example is kept as it is.
+pub fn parse_test_cases(s: &str) -> Result<Vec<TestCase<'_>>, Box<dyn error::Error>> {
+    let name_re = regex!(r"(?m)^== (.+) ==\r?$\n?");
+    let mut cases = vec![];
+    let mut name = None;
+    let mut start = 0;
+    for caps in name_re.captures_iter(s) {
+        let m = caps.get(0).unwrap();
+        let text = &s[start..m.start()];
+        if name.is_some() || !text.trim().is_empty() {
+            cases.push(parse_test_case(name, text)?);
+        }
+        name = Some(caps.get(1).unwrap().as_str());
+        start = m.end();
+    }
+    cases.push(parse_test_case(name, &s[start..])?);
+    Ok(cases)
+}
+
+fn parse_test_case<'a>(
+    name: Option<&'a str>,
+    s: &'a str,
+) -> Result<TestCase<'a>, Box<dyn error::Error>> {
+    let invalid = |reason: &str| match name {
+        Some(name) => format!("Invalid test input format in case {name}: {reason}"),
+        None => format!("Invalid test input format: {reason}"),
+    };
+    let mut consumed = 0;
+    let mut lines = s
+        .split_inclusive('\n')
+        .inspect(|line| consumed += line.len());
+    let mut expected = |part: usize| {
+        let label = format!("Part {part} expected:");
+        let value = lines
+            .next()
+            .and_then(|line| line.strip_prefix(&label))
+            .ok_or_else(|| invalid(&format!("expected a `{label}` line.")))?;
+        answer::parse_value(value, &mut lines).map_err(|reason| invalid(&reason))
+    };
+    let part1_expected = expected(1)?;
+    let part2_expected = expected(2)?;
+    let param_re = regex!(r"^([a-z_][a-z0-9_]*): *(.*)$");
+    let mut params = Params::default();
+    loop {
+        let line = lines
+            .next()
+            .ok_or_else(|| invalid("expected a blank line before the example."))?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let caps = param_re
+            .captures(line)
+            .ok_or_else(|| invalid(&format!("invalid parameter line `{line}`.")))?;
+        params.insert(&caps[1], caps[2].trim());
+    }
+    drop(lines);
+    Ok(TestCase {
+        name,
+        part1_expected,
+        part2_expected,
+        params,
+        text: trim_trailing_blank_lines(&s[consumed..]),
+    })
+}
+
+/// Removes the blank lines that separate one case from the next, keeping the
+/// final line ending of the example if it has one.
+fn trim_trailing_blank_lines(s: &str) -> &str {
+    let trimmed = s.trim_end_matches(['\r', '\n']);
+    let rest = &s[trimmed.len()..];
+    let line_ending = if rest.starts_with("\r\n") {
+        2
+    } else {
+        rest.len().min(1)
+    };
+    &s[..trimmed.len() + line_ending]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_case() {
+        let cases =
+            parse_test_cases("Part 1 expected: 11\nPart 2 expected:\n\n1 2\n3 4\n").unwrap();
+        assert_eq!(
+            cases,
+            vec![TestCase {
+                name: None,
+                part1_expected: Some("11".to_owned()),
+                part2_expected: None,
+                params: Params::default(),
+                text: "1 2\n3 4\n",
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_multiple_cases() {
+        let s = "\
+Part 1 expected: 1
+Part 2 expected: 2
+
+a
+
+== second ==
+Part 1 expected:
+Part 2 expected: 4
+
+b
+c
+== third ==
+Part 1 expected: 5
+Part 2 expected: 6
+
+d";
+        let cases = parse_test_cases(s).unwrap();
+        let summary = cases
+            .iter()
+            .map(|case| {
+                (
+                    case.name,
+                    case.part1_expected.as_deref(),
+                    case.part2_expected.as_deref(),
+                    case.text,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (None, Some("1"), Some("2"), "a\n"),
+                (Some("second"), None, Some("4"), "b\nc\n"),
+                (Some("third"), Some("5"), Some("6"), "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_named_first_case() {
+        let s = "== only ==\nPart 1 expected: 1\nPart 2 expected: 2\n\nx\n";
+        let cases = parse_test_cases(s).unwrap();
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].name, Some("only"));
+        assert_eq!(cases[0].text, "x\n");
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let s = "Part 1 expected: 12\nPart 2 expected:\nwidth: 11\nheight:7\n\nx\n";
+        let cases = parse_test_cases(s).unwrap();
+        assert_eq!(cases[0].params.get("width"), Some("11"));
+        assert_eq!(cases[0].params.get("height"), Some("7"));
+        assert_eq!(cases[0].params.get("depth"), None);
+        assert_eq!(cases[0].text, "x\n");
+    }
+
+    #[test]
+    fn test_parse_multi_line_expected() {
+        let s = "Part 1 expected: \"\"\"\n#..#\n####\n\"\"\"\nPart 2 expected: 3\n\nx\n";
+        let cases = parse_test_cases(s).unwrap();
+        assert_eq!(cases[0].part1_expected.as_deref(), Some("#..#\n####"));
+        assert_eq!(cases[0].part2_expected.as_deref(), Some("3"));
+        assert_eq!(cases[0].text, "x\n");
+
+        let s = "Part 1 expected: \"\"\"\n#..#\nPart 2 expected: 3\n\nx\n";
+        assert!(parse_test_cases(s).is_err());
+    }
+
+    #[test]
+    fn test_parse_crlf() {
+        let s = "\
+Part 1 expected: 1\r
+Part 2 expected: \"\"\"\r
+#.\r
+\"\"\"\r
+width: 3\r
+\r
+a \r
+b\r
+\r
+== second ==\r
+Part 1 expected: 3\r
+Part 2 expected:\r
+\r
+c\r
+";
+        let cases = parse_test_cases(s).unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].part1_expected.as_deref(), Some("1"));
+        assert_eq!(cases[0].part2_expected.as_deref(), Some("#."));
+        assert_eq!(cases[0].params.get("width"), Some("3"));
+        assert_eq!(cases[0].text, "a \r\nb\r\n");
+        assert_eq!(cases[1].name, Some("second"));
+        assert_eq!(cases[1].part1_expected.as_deref(), Some("3"));
+        assert_eq!(cases[1].text, "c\r\n");
+    }
+
+    #[test]
+    fn test_parse_invalid_case() {
+        let s = "Part 1 expected: 1\nPart 2 expected: 2\n\nx\n== bad ==\nnope\n";
+        assert!(parse_test_cases(s).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::harness::{input_dir, Options};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// The modification time of each watched file.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Calls `run`, then calls it again whenever one of the days' input files
+/// changes, clearing the screen first. Source changes only print a message,
+/// since the running binary can't pick them up. Never returns.
+pub fn watch<F>(year: usize, days: &[usize], options: &Options, run: F) -> !
+where
+    F: Fn(),
+{
+    let input_snapshot = || {
+        let mut snapshot = snapshot_dir(&input_dir(year), &|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            days.iter()
+                .any(|day| name.starts_with(&format!("day{day:02}-")) && name.ends_with(".txt"))
+        });
+        for path in [&options.input_path, &options.test_input_path]
+            .into_iter()
+            .flatten()
+        {
+            snapshot.extend(modified(path).map(|time| (path.clone(), time)));
+        }
+        snapshot
+    };
+    let source_snapshot = || snapshot_dir(Path::new("src"), &|_| true);
+    let mut inputs = input_snapshot();
+    let mut sources = source_snapshot();
+    print!("{CLEAR_SCREEN}");
+    run();
+    print_waiting();
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let new_inputs = input_snapshot();
+        let new_sources = source_snapshot();
+        let changed_sources = changed_paths(&sources, &new_sources);
+        if !changed_sources.is_empty() {
+            println!();
+            for path in changed_sources {
+                println!("Changed: {}", path.display());
+            }
+            println!("Rebuild required: restart to run the new code.");
+        }
+        if !changed_paths(&inputs, &new_inputs).is_empty() {
+            print!("{CLEAR_SCREEN}");
+            run();
+            print_waiting();
+        }
+        inputs = new_inputs;
+        sources = new_sources;
+    }
+}
+
+fn print_waiting() {
+    println!();
+    println!("Watching for changes. Press Ctrl-C to stop.");
+}
+
+/// Recursively collects the modification times of the files in a directory
+/// that match `filter`. Files that can't be read are skipped.
+fn snapshot_dir(dir: &Path, filter: &dyn Fn(&Path) -> bool) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return snapshot;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            snapshot.extend(snapshot_dir(&path, filter));
+        } else if filter(&path) {
+            if let Ok(time) = modified(&path) {
+                snapshot.insert(path, time);
+            }
+        }
+    }
+    snapshot
+}
+
+fn modified(path: &Path) -> io::Result<SystemTime> {
+    fs::metadata(path)?.modified()
+}
+
+/// The paths that were added, removed or modified between two snapshots.
+fn changed_paths<'a>(old: &'a Snapshot, new: &'a Snapshot) -> Vec<&'a Path> {
+    let removed = old.keys().filter(|path| !new.contains_key(*path));
+    let added_or_modified = new
+        .iter()
+        .filter(|(path, time)| old.get(*path) != Some(time))
+        .map(|(path, _)| path);
+    let mut paths = removed
+        .chain(added_or_modified)
+        .map(PathBuf::as_path)
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_paths() {
+        let time = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let old = Snapshot::from([
+            (PathBuf::from("a"), time(1)),
+            (PathBuf::from("b"), time(1)),
+            (PathBuf::from("c"), time(1)),
+        ]);
+        let new = Snapshot::from([
+            (PathBuf::from("a"), time(1)),
+            (PathBuf::from("c"), time(2)),
+            (PathBuf::from("d"), time(1)),
+        ]);
+        assert_eq!(
+            changed_paths(&old, &new),
+            vec![Path::new("b"), Path::new("c"), Path::new("d")]
+        );
+        assert!(changed_paths(&old, &old).is_empty());
+    }
+}
@@ -1,12 +1,155 @@
 #![allow(dead_code)]
 extern crate core;
 
-mod days;
 mod harness;
 mod util;
+mod years;
 
-use crate::days::{day, DAY};
+use cli::Args;
+use harness::{
+    answers, bench, cli, export, pool, report, scaffold, watch, Day, DayReport, DayResult, Format,
+    Options, PartReport,
+};
+use std::{env, process};
 
 fn main() {
-    harness::solve(DAY, day::solve_part1, day::solve_part2);
+    let args = cli::parse_args(env::args().skip(1)).unwrap_or_else(|message| {
+        eprintln!("{message}\n\n{}", cli::USAGE);
+        process::exit(2);
+    });
+    if args.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+    if let Some(day) = args.new_day {
+        let year = args.year.unwrap_or(years::latest().number);
+        match scaffold::new_day(year, day) {
+            Ok(paths) => {
+                for path in paths {
+                    println!("Wrote {}", path.display());
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to create day {day}: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+    let year = match args.year {
+        Some(number) => years::get(number).unwrap_or_else(|| {
+            eprintln!("Year {number} is not registered.");
+            process::exit(2);
+        }),
+        None => years::latest(),
+    };
+    let selected = if args.all {
+        year.days.iter().collect::<Vec<_>>()
+    } else if args.days.is_empty() {
+        let Some(latest) = year.days.last() else {
+            eprintln!("Year {} has no registered days.", year.number);
+            process::exit(2);
+        };
+        vec![latest]
+    } else {
+        args.days
+            .iter()
+            .map(|&number| {
+                year.day(number).unwrap_or_else(|| {
+                    eprintln!("Day {number} of {} is not registered.", year.number);
+                    process::exit(2);
+                })
+            })
+            .collect()
+    };
+    if args.watch {
+        let numbers = selected.iter().map(|day| day.number).collect::<Vec<_>>();
+        watch::watch(year.number, &numbers, &args.options, || {
+            run(&args, &selected);
+        });
+    }
+    if !run(&args, &selected) {
+        process::exit(1);
+    }
+}
+
+/// Runs the selected days and prints the results. Returns whether every day
+/// could be run without any of its parts failing.
+fn run(args: &Args, selected: &[&Day]) -> bool {
+    let options = &args.options;
+    // With --all, the days are printed as a summary table at the end instead.
+    let print_days = options.format == Format::Text && !args.all;
+    let show_day = |i: usize, result: &DayResult| {
+        let number = selected[i].number;
+        if print_days && selected.len() > 1 && !options.quiet {
+            if i > 0 {
+                println!();
+            }
+            println!("Day {number}");
+        }
+        match result {
+            Ok(report) => {
+                if print_days {
+                    report::print_day(options, report);
+                }
+                record(options, report);
+            }
+            Err(message) => eprintln!("Day {number} failed: {message}"),
+        }
+    };
+    let results = if args.jobs > 1 {
+        let results = pool::run(selected, options, args.jobs);
+        for (i, result) in results.iter().enumerate() {
+            show_day(i, result);
+        }
+        results
+    } else {
+        selected
+            .iter()
+            .enumerate()
+            .map(|(i, day)| {
+                let result = (day.solve)(options);
+                show_day(i, &result);
+                result
+            })
+            .collect::<Vec<_>>()
+    };
+    let all_ok = results.iter().all(|result| {
+        result
+            .as_ref()
+            .is_ok_and(|report| !report.parts.iter().any(PartReport::failed))
+    });
+    let reports = results.into_iter().flatten().collect::<Vec<_>>();
+    if args.all && options.format == Format::Text {
+        report::print_summary(&reports);
+    }
+    finish(options, &reports);
+    all_ok
+}
+
+/// Prints the structured output, if any, and saves the benchmark baseline.
+fn finish(options: &Options, reports: &[DayReport]) {
+    if let Some(output) = export::export(options.format, reports) {
+        println!("{output}");
+    }
+    save_bench_baseline(options, reports);
+}
+
+fn record(options: &Options, report: &DayReport) {
+    if !options.record {
+        return;
+    }
+    match answers::record(report) {
+        Ok(()) => eprintln!("Recorded answers for day {}.", report.day),
+        Err(e) => eprintln!("Failed to record answers for day {}: {e}", report.day),
+    }
+}
+
+fn save_bench_baseline(options: &Options, reports: &[DayReport]) {
+    if options.bench.is_none() {
+        return;
+    }
+    if let Err(e) = bench::save_baseline(reports) {
+        eprintln!("Failed to save benchmark baseline: {e}");
+    }
 }
@@ -131,7 +131,7 @@ mod tests {
     #[test]
     fn test_neighbors() {
         let grid = Grid(Array2::<i32>::zeros((4, 4)));
-        let neighbors: HashSet<_> = grid.neighbors([1, 2]).into_iter().collect();
+        let neighbors: HashSet<_> = grid.neighbors([1, 2]).collect();
         let expected: HashSet<_> = [
             [0, 1],
             [1, 1],
@@ -158,7 +158,7 @@ mod tests {
         let expected: HashSet<_> = [[0, 2], [2, 2], [1, 1], [1, 3]].into_iter().collect();
         assert_eq!(neighbors, expected);
 
-        let neighbors: HashSet<_> = grid.orthogonal_neighbors([0, 3]).into_iter().collect();
+        let neighbors: HashSet<_> = grid.orthogonal_neighbors([0, 3]).collect();
         let expected: HashSet<_> = [[1, 3], [0, 2]].into_iter().collect();
         assert_eq!(neighbors, expected);
     }
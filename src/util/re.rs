@@ -29,6 +29,95 @@ pub fn parse_with_regex<'a, T: MatchTuple<'a>>(
     T::parse_captures(&caps)
 }
 
+/// Matches a record's regex to a string, then parses each named capture into
+/// the field with the same name.
+pub fn parse_record<'a, T: Record<'a>>(s: &'a str) -> Result<T, Box<dyn error::Error>> {
+    let caps = T::regex()
+        .captures(s)
+        .ok_or("Regex did not match string.")?;
+    T::parse_captures(&caps)
+}
+
+/// A struct whose fields are parsed from the named captures of a regex.
+/// Implement it with `regex_record!`.
+pub trait Record<'a>: Sized {
+    fn regex() -> &'static Regex;
+    fn parse_captures(caps: &Captures<'a>) -> Result<Self, Box<dyn error::Error>>;
+}
+
+/// Declares a struct along with a regex whose named captures hold its fields,
+/// each of which is parsed with `FromStr2`, e.g.
+///
+/// ```ignore
+/// regex_record! {
+///     #[derive(Copy, Clone, Debug)]
+///     struct Gate = r"^(?<in1>\w+) (?<op>\w+) (?<in2>\w+) -> (?<out>\w+)$" {
+///         op: Op,
+///         in1: Name,
+///         in2: Name,
+///         out: Name,
+///     }
+/// }
+/// ```
+///
+/// Parse it with `LineInput::record` or `parse_record`.
+#[macro_export]
+macro_rules! regex_record {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident<$lt:lifetime> = $re:literal {
+            $($field_vis:vis $field:ident: $T:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name<$lt> {
+            $($field_vis $field: $T,)*
+        }
+
+        impl<$lt> $crate::util::re::Record<$lt> for $name<$lt> {
+            $crate::regex_record!(@body $lt, $re, $($field)*);
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident = $re:literal {
+            $($field_vis:vis $field:ident: $T:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($field_vis $field: $T,)*
+        }
+
+        impl<'a> $crate::util::re::Record<'a> for $name {
+            $crate::regex_record!(@body 'a, $re, $($field)*);
+        }
+    };
+    (@body $lt:lifetime, $re:literal, $($field:ident)*) => {
+        fn regex() -> &'static regex::Regex {
+            $crate::regex!($re)
+        }
+
+        fn parse_captures(
+            caps: &regex::Captures<$lt>,
+        ) -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(Self {
+                $(
+                    $field: {
+                        let name = stringify!($field);
+                        let value = caps
+                            .name(name)
+                            .ok_or_else(|| format!("Missing capture {name}."))?
+                            .as_str();
+                        $crate::util::re::FromStr2::from_str(value)
+                            .map_err(|e| format!("Invalid {name}: {value:?} ({e})"))?
+                    },
+                )*
+            })
+        }
+    };
+}
+
 pub trait MatchTuple<'a>: Sized {
     fn len() -> usize;
     fn parse_captures(caps: &Captures<'a>) -> Result<Self, Box<dyn error::Error>>;
@@ -137,4 +226,23 @@ mod tests {
         assert_eq!(last_name, "Luthor");
         assert_eq!(count, 40);
     }
+
+    regex_record! {
+        #[derive(Debug)]
+        struct Theft<'a> = r"^(?<thief>.+) stole (?<count>\d+) (?<item>\w+)\.$" {
+            thief: Str<'a>,
+            item: String,
+            count: usize,
+        }
+    }
+
+    #[test]
+    fn test_parse_record() {
+        let theft = parse_record::<Theft>("Lex Luthor stole 40 cakes.").unwrap();
+        assert_eq!(theft.thief.as_str(), "Lex Luthor");
+        assert_eq!((theft.item.as_str(), theft.count), ("cakes", 40));
+        assert!(parse_record::<Theft>("Lex Luthor stole some cakes.").is_err());
+        let error = parse_record::<Theft>("Lex Luthor stole 99999999999999999999 cakes.");
+        assert!(error.unwrap_err().to_string().starts_with("Invalid count"));
+    }
 }
@@ -0,0 +1,73 @@
+use crate::harness::Year;
+
+/// Adds each day's module to the year's `DAYS` registry, so that every day
+/// can be selected at runtime. Days marked `(parsed)` have a separate `parse`
+/// function whose output is passed to both parts. The modules themselves are
+/// declared with plain `mod` items next to the invocation, so that rustfmt
+/// formats them.
+macro_rules! register_days {
+    (@solve $options:ident, $year:literal, $module:ident, $number:literal) => {
+        $crate::harness::solve(
+            $options,
+            $year,
+            $number,
+            $module::solve_part1,
+            $module::solve_part2,
+        )
+    };
+    (@solve $options:ident, $year:literal, $module:ident, $number:literal, parsed) => {
+        $crate::harness::solve_parsed(
+            $options,
+            $year,
+            $number,
+            $module::parse,
+            $module::solve_part1,
+            $module::solve_part2,
+        )
+    };
+    (
+        year = $year:literal;
+        $($module:ident = $number:literal $(($phase:ident))?),* $(,)?
+    ) => {
+        pub const DAYS: &[$crate::harness::Day] = &[
+            $(
+                $crate::harness::Day {
+                    number: $number,
+                    solve: |options| {
+                        register_days!(@solve options, $year, $module, $number $(, $phase)?)
+                    },
+                },
+            )*
+        ];
+    };
+}
+
+/// Adds each year's module, which registers its days with `register_days!`,
+/// to the `YEARS` registry.
+macro_rules! register_years {
+    ($($module:ident = $number:literal),* $(,)?) => {
+        pub const YEARS: &[Year] = &[
+            $(
+                Year {
+                    number: $number,
+                    days: $module::DAYS,
+                },
+            )*
+        ];
+    };
+}
+
+pub mod y2024;
+
+register_years! {
+    y2024 = 2024,
+}
+
+pub fn get(number: usize) -> Option<&'static Year> {
+    YEARS.iter().find(|year| year.number == number)
+}
+
+/// The most recent year, which runs when no year is given.
+pub fn latest() -> &'static Year {
+    YEARS.last().unwrap()
+}
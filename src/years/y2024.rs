@@ -0,0 +1,54 @@
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+register_days! {
+    year = 2024;
+    day01 = 1,
+    day02 = 2,
+    day03 = 3,
+    day04 = 4,
+    day05 = 5,
+    day06 = 6,
+    day07 = 7,
+    day08 = 8,
+    day09 = 9,
+    day10 = 10,
+    day11 = 11,
+    day12 = 12,
+    day13 = 13,
+    day14 = 14,
+    day15 = 15,
+    day16 = 16 (parsed),
+    day17 = 17,
+    day18 = 18,
+    day19 = 19,
+    day20 = 20 (parsed),
+    day21 = 21,
+    day22 = 22,
+    day23 = 23,
+    day24 = 24,
+    day25 = 25,
+}
@@ -36,7 +36,7 @@ pub fn solve_part2(input: RawInput) -> usize {
 }
 
 fn parse(input: RawInput) -> (HashSet<(usize, usize)>, Vec<Vec<usize>>) {
-    let (rules, updates) = input.split_once_on_empty_line();
+    let [rules, updates] = input.sections_n();
     let rules = rules
         .per_line(|line| line.parse_with_regex(regex!(r"(\d+)\|(\d+)")))
         .collect();
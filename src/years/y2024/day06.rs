@@ -1,6 +1,9 @@
 use std::{collections::HashSet, hash::Hash};
 
-use crate::{harness::input::RawInput, util::grid::Grid};
+use crate::{
+    harness::{self, input::RawInput},
+    util::grid::Grid,
+};
 
 const NEG_1: usize = -1_isize as usize;
 
@@ -24,6 +27,7 @@ pub fn solve_part2(input: RawInput) -> usize {
         let mut walls = walls.clone();
         walls.insert(ij);
         loop {
+            input.check_cancelled();
             if seen_states.contains(&state) {
                 count += 1;
                 break;
@@ -74,6 +78,7 @@ fn get_visited(
 ) -> HashSet<[usize; 2]> {
     let mut visited = HashSet::new();
     loop {
+        harness::check_cancelled();
         visited.insert(state.pos);
         let Some(next_state) = get_next_state(grid, walls, state) else {
             return visited;
@@ -17,7 +17,7 @@ fn solve(input: RawInput, times: usize) -> usize {
         .into_iter()
         .map(|n| get_count(n, times, &mut cache))
         .sum();
-    println!("{:?}", cache.len());
+    input.debug(format!("Cache size: {}", cache.len()));
     out
 }
 
@@ -41,7 +41,7 @@ fn get_count(n: usize, times: usize, cache: &mut HashMap<(usize, usize), usize>)
 
 fn split_stone(n: usize) -> Option<[usize; 2]> {
     let s = n.to_string();
-    if s.len() % 2 == 0 {
+    if s.len().is_multiple_of(2) {
         let (a, b) = s.split_at(s.len() / 2);
         Some([a.parse().unwrap(), b.parse().unwrap()])
     } else {
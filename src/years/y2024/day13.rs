@@ -1,4 +1,4 @@
-use crate::{harness::input::RawInput, regex, util::re};
+use crate::harness::input::RawInput;
 
 pub fn solve_part1(input: RawInput) -> usize {
     solve(input, 0)
@@ -9,21 +9,11 @@ pub fn solve_part2(input: RawInput) -> usize {
 }
 
 fn solve(input: RawInput, added_position: isize) -> usize {
-    let button_regex = regex!(r"Button .: X\+(\d+), Y\+(\d+)");
-    let prize_regex = regex!(r"Prize: X=(\d+), Y=(\d+)");
     input
-        .as_str()
-        .split("\n\n")
+        .sections()
+        .into_iter()
         .map(|claw| {
-            let mut lines = claw.lines();
-            let (ax, ay) =
-                re::parse_with_regex::<(isize, isize)>(button_regex, lines.next().unwrap())
-                    .unwrap();
-            let (bx, by) =
-                re::parse_with_regex::<(isize, isize)>(button_regex, lines.next().unwrap())
-                    .unwrap();
-            let (px, py) =
-                re::parse_with_regex::<(isize, isize)>(prize_regex, lines.next().unwrap()).unwrap();
+            let [ax, ay, bx, by, px, py] = claw.ints_n::<isize, 6>();
             let (px, py) = (px + added_position, py + added_position);
             let determinant = ax * by - bx * ay;
             if determinant == 0 {
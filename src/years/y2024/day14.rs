@@ -0,0 +1,91 @@
+use std::io::Write;
+use std::{collections::HashSet, fs::File};
+
+use crate::harness::input::RawInput;
+
+const WIDTH: isize = 101;
+const HEIGHT: isize = 103;
+
+pub fn solve_part1(input: RawInput) -> usize {
+    let width = input.param("width", WIDTH);
+    let height = input.param("height", HEIGHT);
+    let robots = input
+        .per_line(|line| line.ints_n::<isize, 4>())
+        .map(|[px, py, vx, vy]| {
+            [
+                pos_mod(px + 100 * vx, width),
+                pos_mod(py + 100 * vy, height),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mid_x = width / 2;
+    let mid_y = height / 2;
+    let upleft = robots
+        .iter()
+        .copied()
+        .filter(|&[x, y]| x < mid_x && y < mid_y)
+        .count();
+    let upright = robots
+        .iter()
+        .copied()
+        .filter(|&[x, y]| x > mid_x && y < mid_y)
+        .count();
+    let downleft = robots
+        .iter()
+        .copied()
+        .filter(|&[x, y]| x < mid_x && y > mid_y)
+        .count();
+    let downright = robots
+        .iter()
+        .copied()
+        .filter(|&[x, y]| x > mid_x && y > mid_y)
+        .count();
+    upleft * upright * downleft * downright
+}
+
+pub fn solve_part2(input: RawInput) -> usize {
+    let width = input.param("width", WIDTH);
+    let height = input.param("height", HEIGHT);
+    let robots = input
+        .per_line(|line| line.ints_n::<isize, 4>())
+        .collect::<Vec<_>>();
+    // Dumps frames to search for the tree by eye, but only while debugging.
+    if input.is_debugging() {
+        let mut file = File::create("day14-out.txt").unwrap();
+        for time in 0..10000 {
+            if time % 101 != 77 {
+                continue;
+            }
+            let new_robots = robots
+                .iter()
+                .map(|&[px, py, vx, vy]| {
+                    [
+                        pos_mod(px + time * vx, width),
+                        pos_mod(py + time * vy, height),
+                    ]
+                })
+                .collect::<HashSet<_>>();
+            writeln!(&mut file, "Time: {time}\n").unwrap();
+            for y in 0..height {
+                for x in 0..width {
+                    write!(
+                        &mut file,
+                        "{}",
+                        if new_robots.contains(&[x, y]) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    )
+                    .unwrap();
+                }
+                writeln!(&mut file).unwrap();
+            }
+        }
+    }
+    todo!()
+}
+
+fn pos_mod(a: isize, b: isize) -> isize {
+    (a % b + b) % b
+}
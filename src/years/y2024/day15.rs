@@ -6,7 +6,7 @@ use crate::{
 };
 
 pub fn solve_part1(input: RawInput) -> usize {
-    let (grid, dirs) = input.split_once_on_empty_line();
+    let [grid, dirs] = input.sections_n();
     let grid = Grid::parse_bytes(grid.as_str());
     let walls = grid
         .indices()
@@ -34,6 +34,7 @@ pub fn solve_part1(input: RawInput) -> usize {
         let mut loc = robot.add(dir);
         let mut box_count = 0;
         while boxes.contains(&loc) {
+            input.check_cancelled();
             box_count += 1;
             loc = loc.add(dir);
         }
@@ -50,7 +51,7 @@ pub fn solve_part1(input: RawInput) -> usize {
 }
 
 pub fn solve_part2(input: RawInput) -> usize {
-    let (grid, dirs) = input.split_once_on_empty_line();
+    let [grid, dirs] = input.sections_n();
     let grid = Grid::parse_bytes(grid.as_str());
     let walls = grid
         .indices()
@@ -82,6 +83,7 @@ pub fn solve_part2(input: RawInput) -> usize {
             let mut loc = robot.add(dir);
             let mut pushed_boxes = vec![];
             while let Some(overlapping_box) = get_overlapping_box(&boxes, loc) {
+                input.check_cancelled();
                 pushed_boxes.push(overlapping_box);
                 loc = loc.add(dir.scalar_mul(2));
             }
@@ -107,6 +109,7 @@ pub fn solve_part2(input: RawInput) -> usize {
             just_pushed.insert(first_pushed_box);
             let mut hit_wall = false;
             while !just_pushed.is_empty() {
+                input.check_cancelled();
                 let mut new_just_pushed = HashSet::new();
                 for &pushed_box in &just_pushed {
                     let pushed_loc = pushed_box.add(dir);
@@ -143,7 +146,9 @@ pub fn solve_part2(input: RawInput) -> usize {
             boxes.extend(to_add);
         }
     }
-    print_grid(&walls, &boxes, robot);
+    if input.is_debugging() {
+        input.debug(format_grid(&walls, &boxes, robot));
+    }
     boxes.into_iter().map(|[i, j]| 100 * i + j).sum()
 }
 
@@ -159,7 +164,11 @@ fn get_overlapping_box(boxes: &HashSet<[usize; 2]>, [i, j]: [usize; 2]) -> Optio
     }
 }
 
-fn print_grid(walls: &HashSet<[usize; 2]>, boxes: &HashSet<[usize; 2]>, robot: [usize; 2]) {
+fn format_grid(
+    walls: &HashSet<[usize; 2]>,
+    boxes: &HashSet<[usize; 2]>,
+    robot: [usize; 2],
+) -> String {
     let height = walls.iter().map(|&[i, _]| i).max().unwrap() + 1;
     let width = walls.iter().map(|&[_, j]| j).max().unwrap() + 1;
     let mut lines = vec![];
@@ -174,12 +183,11 @@ fn print_grid(walls: &HashSet<[usize; 2]>, boxes: &HashSet<[usize; 2]>, robot: [
         lines[bawks[0]][bawks[1] + 1] = ']';
     }
     lines[robot[0]][robot[1]] = '@';
-    let out = lines
+    lines
         .into_iter()
         .map(|line| line.into_iter().collect::<String>())
         .collect::<Vec<_>>()
-        .join("\n");
-    println!("{out}\n");
+        .join("\n")
 }
 
 // fn get_other_box_spot([i, j]: [usize; 2]) -> [usize; 2] {
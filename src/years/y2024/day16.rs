@@ -11,7 +11,13 @@ use crate::{
     },
 };
 
-pub fn solve_part1(input: RawInput) -> usize {
+pub struct Maze {
+    walls: HashSet<[usize; 2]>,
+    start: [usize; 2],
+    end: [usize; 2],
+}
+
+pub fn parse(input: RawInput) -> Maze {
     let grid = Grid::parse_bytes(input.as_str());
     let walls = grid
         .indices()
@@ -19,6 +25,12 @@ pub fn solve_part1(input: RawInput) -> usize {
         .collect::<HashSet<_>>();
     let start = grid.indices().find(|&ij| grid[ij] == b'S').unwrap();
     let end = grid.indices().find(|&ij| grid[ij] == b'E').unwrap();
+    Maze { walls, start, end }
+}
+
+pub fn solve_part1(maze: &Maze) -> usize {
+    let Maze { walls, start, end } = maze;
+    let (start, end) = (*start, *end);
 
     let result = dijkstra::search(
         (start, [0, 1]),
@@ -39,14 +51,9 @@ pub fn solve_part1(input: RawInput) -> usize {
     result.goal_state().unwrap().distance
 }
 
-pub fn solve_part2(input: RawInput) -> usize {
-    let grid = Grid::parse_bytes(input.as_str());
-    let walls = grid
-        .indices()
-        .filter(|&ij| grid[ij] == b'#')
-        .collect::<HashSet<_>>();
-    let start = grid.indices().find(|&ij| grid[ij] == b'S').unwrap();
-    let end = grid.indices().find(|&ij| grid[ij] == b'E').unwrap();
+pub fn solve_part2(maze: &Maze) -> usize {
+    let Maze { walls, start, end } = maze;
+    let (start, end) = (*start, *end);
 
     let result = day16dijkstra::search(
         (start, [0, 1]),
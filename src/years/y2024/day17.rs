@@ -70,41 +70,9 @@ impl State {
     }
 
     fn parse(input: RawInput) -> Self {
-        let mut lines = input.as_str().lines();
-        let a = lines
-            .next()
-            .unwrap()
-            .split_once(": ")
-            .unwrap()
-            .1
-            .parse()
-            .unwrap();
-        let b = lines
-            .next()
-            .unwrap()
-            .split_once(": ")
-            .unwrap()
-            .1
-            .parse()
-            .unwrap();
-        let c = lines
-            .next()
-            .unwrap()
-            .split_once(": ")
-            .unwrap()
-            .1
-            .parse()
-            .unwrap();
-        lines.next().unwrap();
-        let instructions = lines
-            .next()
-            .unwrap()
-            .split_once(": ")
-            .unwrap()
-            .1
-            .split(',')
-            .map(|c| c.parse().unwrap())
-            .collect();
+        let [registers, program] = input.sections_n();
+        let [a, b, c] = registers.ints_n();
+        let instructions = program.ints();
         Self {
             a,
             b,
@@ -1,32 +1,36 @@
 use std::collections::HashSet;
 
 use crate::{
-    harness::input::RawInput,
+    harness::{answer::CommaSeparated, input::RawInput},
     util::{coords::Coord2, search::bfs},
 };
 
-const WIDTH: usize = 70;
-const HEIGHT: usize = 70;
+/// The number of coordinates along each side of the memory space.
+const SIZE: usize = 71;
+/// The number of bytes that have fallen for part 1.
+const BYTES: usize = 1024;
 
 pub fn solve_part1(input: RawInput) -> usize {
+    let size = input.param("size", SIZE);
+    let bytes = input.param("bytes", BYTES);
     let corrupted = parse_corrupted(input);
-    get_path_len(&corrupted[..1024].iter().copied().collect()).unwrap()
+    get_path_len(&corrupted[..bytes].iter().copied().collect(), size).unwrap()
 }
 
-pub fn solve_part2(input: RawInput) -> usize {
+pub fn solve_part2(input: RawInput) -> CommaSeparated<Coord2<usize>> {
+    let size = input.param("size", SIZE);
     let corrupted = parse_corrupted(input);
     let mut low = 0;
     let mut high = corrupted.len();
     while low < high - 1 {
         let mid = (low + high) / 2;
-        if get_path_len(&corrupted[..mid].iter().copied().collect()).is_some() {
+        if get_path_len(&corrupted[..mid].iter().copied().collect(), size).is_some() {
             low = mid;
         } else {
             high = mid;
         }
     }
-    println!("{:?}", corrupted[low]);
-    todo!()
+    CommaSeparated(corrupted[low])
 }
 
 fn parse_corrupted(input: RawInput) -> Vec<Coord2<usize>> {
@@ -38,16 +42,16 @@ fn parse_corrupted(input: RawInput) -> Vec<Coord2<usize>> {
         .collect()
 }
 
-fn get_path_len(corrupted: &HashSet<Coord2<usize>>) -> Option<usize> {
+fn get_path_len(corrupted: &HashSet<Coord2<usize>>, size: usize) -> Option<usize> {
     bfs::search(
         Coord2(0, 0),
         |&p| {
             p.orthogonal_neighbors()
                 .into_iter()
-                .filter(|&n| !corrupted.contains(&n) && n.0 <= 70 && n.1 <= 70)
+                .filter(|&n| !corrupted.contains(&n) && n.0 < size && n.1 < size)
                 .collect::<Vec<_>>()
         },
-        |&p| p == Coord2(70, 70),
+        |&p| p == Coord2(size - 1, size - 1),
     )
     .path_to_goal()
     .map(|path| path.len() - 1)
@@ -17,7 +17,7 @@ pub fn solve_part2(input: RawInput) -> usize {
 }
 
 fn parse(input: RawInput) -> (Vec<Vec<char>>, Vec<Vec<char>>) {
-    let (towels, goals) = input.split_once_on_empty_line();
+    let [towels, goals] = input.sections_n();
     let towels = towels
         .single_line(|line| line.split::<String>(", "))
         .into_iter()
@@ -1,25 +1,51 @@
 use std::collections::HashMap;
 
 use crate::{
-    harness::input::RawInput,
+    harness::{self, input::RawInput},
     util::{grid::Grid, search::bfs},
 };
 
-pub fn solve_part1(input: RawInput) -> usize {
-    solve(input, 2)
-}
+/// The number of picoseconds a cheat must save to be counted.
+const MIN_SAVINGS: usize = 100;
 
-pub fn solve_part2(input: RawInput) -> usize {
-    solve(input, 20)
+pub struct Racetrack {
+    grid: Grid<char>,
+    start: [usize; 2],
+    end: [usize; 2],
+    min_savings: usize,
 }
 
-pub fn solve(input: RawInput, max_cheat_time: usize) -> usize {
+pub fn parse(input: RawInput) -> Racetrack {
     let grid = Grid::parse_chars(input.as_str());
     let start = grid.indices().find(|&idx| grid[idx] == 'S').unwrap();
     let end = grid.indices().find(|&idx| grid[idx] == 'E').unwrap();
-    let distances_from_start = get_shortest_distances(&grid, start);
-    let distances_from_end = get_shortest_distances(&grid, end);
-    let no_cheat_best = distances_from_start[&end];
+    Racetrack {
+        grid,
+        start,
+        end,
+        min_savings: input.param("min_savings", MIN_SAVINGS),
+    }
+}
+
+pub fn solve_part1(racetrack: &Racetrack) -> usize {
+    solve(racetrack, 2)
+}
+
+pub fn solve_part2(racetrack: &Racetrack) -> usize {
+    solve(racetrack, 20)
+}
+
+pub fn solve(racetrack: &Racetrack, max_cheat_time: usize) -> usize {
+    let Racetrack {
+        grid,
+        start,
+        end,
+        min_savings,
+    } = racetrack;
+    let distances_from_start = get_shortest_distances(grid, *start);
+    let distances_from_end = get_shortest_distances(grid, *end);
+    let no_cheat_best = distances_from_start[end];
+    let _span = harness::span("cheat scan");
     let mut count = 0;
     for ij1 in grid.indices() {
         if grid[ij1] == '#' {
@@ -41,7 +67,7 @@ pub fn solve(input: RawInput, max_cheat_time: usize) -> usize {
                 }
                 let cheated_best =
                     distances_from_start[&ij1] + distances_from_end[&ij2] + cheat_duration;
-                if cheated_best + 100 <= no_cheat_best {
+                if cheated_best + min_savings <= no_cheat_best {
                     count += 1;
                 }
             }
@@ -51,6 +77,7 @@ pub fn solve(input: RawInput, max_cheat_time: usize) -> usize {
 }
 
 fn get_shortest_distances(grid: &Grid<char>, start: [usize; 2]) -> HashMap<[usize; 2], usize> {
+    let _span = harness::span("shortest distances");
     bfs::search(
         start,
         |&ij| {
@@ -2,7 +2,10 @@ use std::{collections::HashMap, convert::Infallible, str::FromStr};
 
 use arrayvec::ArrayString;
 
-use crate::{harness::input::RawInput, regex};
+use crate::{
+    harness::{self, input::RawInput},
+    regex_record,
+};
 
 type Name = ArrayString<3>;
 
@@ -36,12 +39,14 @@ impl Op {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-struct Gate {
-    op: Op,
-    in1: Name,
-    in2: Name,
-    out: Name,
+regex_record! {
+    #[derive(Copy, Clone, Debug)]
+    struct Gate = r"^(?<in1>\w+) (?<op>\w+) (?<in2>\w+) -> (?<out>\w+)$" {
+        op: Op,
+        in1: Name,
+        in2: Name,
+        out: Name,
+    }
 }
 
 pub fn solve_part1(input: RawInput) -> usize {
@@ -113,6 +118,7 @@ fn eval(initial_wires: &HashMap<Name, bool>, gates: &[Gate]) -> usize {
 }
 
 fn eval_sum(gates: &[Gate], x: usize, y: usize) -> usize {
+    let _span = harness::span("eval_sum");
     let mut initial_wires = HashMap::<Name, bool>::new();
     for (i, bit) in to_bits(x).into_iter().enumerate() {
         initial_wires.insert(wire_name('x', i), bit);
@@ -138,6 +144,7 @@ fn get_wires_at_digit(gates: &[Gate], i: usize) -> Vec<Name> {
 }
 
 fn fix_error(gates: &[Gate], error_index: usize) -> Option<[Name; 2]> {
+    let _span = harness::span("fix_error");
     let wires_to_mess_with = get_wires_at_digit(gates, error_index);
     for i in 0..wires_to_mess_with.len() {
         for j in i + 1..wires_to_mess_with.len() {
@@ -200,7 +207,7 @@ fn to_bits(mut x: usize) -> Vec<bool> {
 }
 
 fn parse(input: RawInput) -> (HashMap<Name, bool>, Vec<Gate>) {
-    let (initial_wires, gates) = input.split_once_on_empty_line();
+    let [initial_wires, gates] = input.sections_n();
     let initial_wires = initial_wires
         .per_line(|line| {
             let (name, value) = line.split_once(": ");
@@ -209,12 +216,6 @@ fn parse(input: RawInput) -> (HashMap<Name, bool>, Vec<Gate>) {
             (name, value)
         })
         .collect();
-    let gates = gates
-        .per_line(|line| {
-            let (in1, op, in2, out) =
-                line.parse_with_regex::<(Name, Op, Name, Name)>(regex!(r"(.+) (.+) (.+) -> (.+)"));
-            Gate { op, in1, in2, out }
-        })
-        .collect();
+    let gates = gates.per_line(|line| line.record::<Gate>()).collect();
     (initial_wires, gates)
 }
@@ -3,8 +3,8 @@ use crate::{harness::input::RawInput, util::grid::Grid};
 pub fn solve_part1(input: RawInput) -> usize {
     let mut locks = vec![];
     let mut keys = vec![];
-    input.as_str().split("\n\n").for_each(|group| {
-        let grid = Grid::parse_chars(group);
+    input.sections().into_iter().for_each(|section| {
+        let grid = Grid::parse_chars(section.as_str());
         if grid[[0, 0]] == '#' {
             let mut lock = [0; 5];
             for j in 0..5 {